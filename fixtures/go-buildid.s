@@ -0,0 +1,8 @@
+# the note the Go linker emits, with the name padded to 4 bytes
+	.section .note.go.buildid, "a", @note
+	.balign 4
+	.long 4
+	.long 12
+	.long 4
+	.asciz "Go\0"
+	.ascii "abc/def/ghij"
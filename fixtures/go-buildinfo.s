@@ -0,0 +1,32 @@
+# the build info layout from before Go 1.18, which points at Go string headers
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .go.buildinfo, "aw"
+	.balign 16
+	.ascii "\xff Go buildinf:"
+	.byte 8
+	.byte 0
+	.quad version
+	.quad module_info
+
+	.section .rodata
+	.balign 8
+version:
+	.quad version_bytes
+	.quad version_end - version_bytes
+module_info:
+	.quad module_info_bytes
+	.quad module_info_end - module_info_bytes
+
+version_bytes:
+	.ascii "go1.17.2"
+version_end:
+module_info_bytes:
+# the sentinels Go wraps the module info in
+	.byte 0x30, 0x77, 0xaf, 0x0c, 0x92, 0x74, 0x08, 0x02, 0x41, 0xe1, 0xc1, 0x07, 0xe6, 0xd6, 0x18, 0xe6
+	.ascii "path\texample.com/hello\nmod\texample.com/hello\t(devel)\t\n"
+	.byte 0xf9, 0x32, 0x43, 0x31, 0x86, 0x18, 0x20, 0x72, 0x00, 0x82, 0x42, 0x10, 0x41, 0x16, 0xd8, 0xf2
+module_info_end:
@@ -0,0 +1,9 @@
+#include <stdio.h>
+
+// built with the stack protector and fortify source, which routes this through `__sprintf_chk`
+int main(int argc, char **argv) {
+	char buffer[16];
+	sprintf(buffer, "%s", argv[0]);
+	puts(buffer);
+	return argc;
+}
@@ -0,0 +1,14 @@
+	.text
+	.globl answer
+	.type answer, @function
+answer:
+	jr $ra
+	li $v0, 42
+	.size answer, . - answer
+
+	.data
+	.globl value
+	.type value, @object
+value:
+	.8byte 0x0102030405060708
+	.size value, 8
@@ -0,0 +1,32 @@
+#include <stdio.h>
+
+int counter = 3;
+static int calls;
+__attribute__((visibility("hidden"))) int hidden_value = 1;
+__attribute__((visibility("protected"))) int protected_value = 2;
+__attribute__((visibility("internal"))) int internal_value = 3;
+__thread int per_thread = 4;
+
+const char greeting[] = "Hello from the sample fixture";
+const char *message = greeting;
+
+// a tentative definition, merged with the same name in other objects at link time
+__attribute__((common)) int scratch[4];
+
+// a constant with no storage, its value is the number itself
+__asm__(".globl table_size\n.set table_size, 64");
+
+int add(int a, int b) {
+	calls++;
+	return a + b;
+}
+
+int main(void) {
+	puts(message);
+	return add(counter, per_thread) == 0;
+}
+
+// can be replaced by a strong definition in another object
+__attribute__((weak)) int fallback(void) {
+	return -1;
+}
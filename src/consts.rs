@@ -0,0 +1,240 @@
+pub const ELFMAG: [u8; 4] = [0x7F, b'E', b'L', b'F'];
+
+pub const ELFCLASSNONE: u8 = 0;
+pub const ELFCLASS32: u8 = 1;
+pub const ELFCLASS64: u8 = 2;
+
+pub const ELFDATANONE: u8 = 0;
+pub const ELFDATA2LSB: u8 = 1;
+pub const ELFDATA2MSB: u8 = 2;
+
+pub const EV_NONE: u8 = 0;
+pub const EV_CURRENT: u8 = 1;
+
+pub const PN_XNUM: u16 = 0xFFFF;
+
+pub const SHN_UNDEF: u16 = 0x0000;
+pub const SHN_LORESERVE: u16 = 0xFF00;
+pub const SHN_LOPROC: u16 = 0xFF00;
+pub const SHN_HIPROC: u16 = 0xFF1F;
+pub const SHN_LOOS: u16 = 0xFF20;
+pub const SHN_HIOS: u16 = 0xFF3F;
+pub const SHN_ABS: u16 = 0xFFF1;
+pub const SHN_COMMON: u16 = 0xFFF2;
+pub const SHN_XINDEX: u16 = 0xFFFF;
+pub const SHN_HIRESERVE: u16 = 0xFFFF;
+
+pub const SHT_NULL: u32 = 0;
+pub const SHT_PROGBITS: u32 = 1;
+pub const SHT_SYMTAB: u32 = 2;
+pub const SHT_STRTAB: u32 = 3;
+pub const SHT_RELA: u32 = 4;
+pub const SHT_HASH: u32 = 5;
+pub const SHT_DYNAMIC: u32 = 6;
+pub const SHT_NOTE: u32 = 7;
+pub const SHT_NOBITS: u32 = 8;
+pub const SHT_REL: u32 = 9;
+pub const SHT_SHLIB: u32 = 10;
+pub const SHT_DYNSYM: u32 = 11;
+pub const SHT_INIT_ARRAY: u32 = 14;
+pub const SHT_FINI_ARRAY: u32 = 15;
+pub const SHT_PREINIT_ARRAY: u32 = 16;
+pub const SHT_GROUP: u32 = 17;
+pub const SHT_SYMTAB_SHNDX: u32 = 18;
+pub const SHT_RELR: u32 = 19;
+pub const SHT_LOOS: u32 = 0x6000_0000;
+pub const SHT_LLVM_ODRTAB: u32 = 0x6FFF_4C00;
+pub const SHT_LLVM_LINKER_OPTIONS: u32 = 0x6FFF_4C01;
+pub const SHT_LLVM_ADDRSIG: u32 = 0x6FFF_4C03;
+pub const SHT_LLVM_DEPENDENT_LIBRARIES: u32 = 0x6FFF_4C04;
+pub const SHT_LLVM_SYMPART: u32 = 0x6FFF_4C05;
+pub const SHT_LLVM_CALL_GRAPH_PROFILE: u32 = 0x6FFF_4C09;
+pub const SHT_LLVM_BB_ADDR_MAP: u32 = 0x6FFF_4C0A;
+pub const SHT_LLVM_OFFLOADING: u32 = 0x6FFF_4C0B;
+pub const SHT_LLVM_LTO: u32 = 0x6FFF_4C0C;
+pub const SHT_GNU_ATTRIBUTES: u32 = 0x6FFF_FFF5;
+pub const SHT_GNU_HASH: u32 = 0x6FFF_FFF6;
+pub const SHT_GNU_LIBLIST: u32 = 0x6FFF_FFF7;
+pub const SHT_GNU_VERDEF: u32 = 0x6FFF_FFFD;
+pub const SHT_GNU_VERNEED: u32 = 0x6FFF_FFFE;
+pub const SHT_GNU_VERSYM: u32 = 0x6FFF_FFFF;
+pub const SHT_HIOS: u32 = 0x6FFF_FFFF;
+pub const SHT_LOPROC: u32 = 0x7000_0000;
+pub const SHT_MIPS_ABIFLAGS: u32 = 0x7000_002A;
+pub const SHT_HIPROC: u32 = 0x7FFF_FFFF;
+pub const SHT_LOUSER: u32 = 0x8000_0000;
+pub const SHT_HIUSER: u32 = 0xFFFF_FFFF;
+
+pub const SHF_WRITE: u64 = 0x001;
+pub const SHF_ALLOC: u64 = 0x002;
+pub const SHF_EXECINSTR: u64 = 0x004;
+pub const SHF_MERGE: u64 = 0x010;
+pub const SHF_STRINGS: u64 = 0x020;
+pub const SHF_INFO_LINK: u64 = 0x040;
+pub const SHF_LINK_ORDER: u64 = 0x080;
+pub const SHF_OS_NONCONFORMING: u64 = 0x100;
+pub const SHF_GROUP: u64 = 0x200;
+pub const SHF_TLS: u64 = 0x400;
+pub const SHF_COMPRESSED: u64 = 0x800;
+pub const SHF_MASKOS: u64 = 0x0FF0_0000;
+pub const SHF_MASKPROC: u64 = 0xF000_0000;
+
+pub const PT_NULL: u32 = 0;
+pub const PT_LOAD: u32 = 1;
+pub const PT_DYNAMIC: u32 = 2;
+pub const PT_INTERP: u32 = 3;
+pub const PT_NOTE: u32 = 4;
+pub const PT_SHLIB: u32 = 5;
+pub const PT_PHDR: u32 = 6;
+pub const PT_TLS: u32 = 7;
+pub const PT_LOOS: u32 = 0x6000_0000;
+pub const PT_GNU_EH_FRAME: u32 = 0x6474_E550;
+pub const PT_GNU_STACK: u32 = 0x6474_E551;
+pub const PT_GNU_RELRO: u32 = 0x6474_E552;
+pub const PT_GNU_PROPERTY: u32 = 0x6474_E553;
+pub const PT_HIOS: u32 = 0x6FFF_FFFF;
+pub const PT_LOPROC: u32 = 0x7000_0000;
+pub const PT_HIPROC: u32 = 0x7FFF_FFFF;
+
+pub const PF_X: u32 = 0x1;
+pub const PF_W: u32 = 0x2;
+pub const PF_R: u32 = 0x4;
+
+pub const DT_NULL: u64 = 0;
+pub const DT_NEEDED: u64 = 1;
+pub const DT_PLTRELSZ: u64 = 2;
+pub const DT_PLTGOT: u64 = 3;
+pub const DT_HASH: u64 = 4;
+pub const DT_STRTAB: u64 = 5;
+pub const DT_SYMTAB: u64 = 6;
+pub const DT_RELA: u64 = 7;
+pub const DT_RELASZ: u64 = 8;
+pub const DT_RELAENT: u64 = 9;
+pub const DT_STRSZ: u64 = 10;
+pub const DT_SYMENT: u64 = 11;
+pub const DT_INIT: u64 = 12;
+pub const DT_FINI: u64 = 13;
+pub const DT_SONAME: u64 = 14;
+pub const DT_RPATH: u64 = 15;
+pub const DT_SYMBOLIC: u64 = 16;
+pub const DT_REL: u64 = 17;
+pub const DT_RELSZ: u64 = 18;
+pub const DT_RELENT: u64 = 19;
+pub const DT_PLTREL: u64 = 20;
+pub const DT_DEBUG: u64 = 21;
+pub const DT_TEXTREL: u64 = 22;
+pub const DT_JMPREL: u64 = 23;
+pub const DT_BIND_NOW: u64 = 24;
+pub const DT_INIT_ARRAY: u64 = 25;
+pub const DT_FINI_ARRAY: u64 = 26;
+pub const DT_INIT_ARRAYSZ: u64 = 27;
+pub const DT_FINI_ARRAYSZ: u64 = 28;
+pub const DT_RUNPATH: u64 = 29;
+pub const DT_FLAGS: u64 = 30;
+pub const DT_PREINIT_ARRAY: u64 = 32;
+pub const DT_PREINIT_ARRAYSZ: u64 = 33;
+pub const DT_SYMTAB_SHNDX: u64 = 34;
+pub const DT_RELRSZ: u64 = 35;
+pub const DT_RELR: u64 = 36;
+pub const DT_RELRENT: u64 = 37;
+pub const DT_LOOS: u64 = 0x6000_000D;
+pub const DT_GNU_HASH: u64 = 0x6FFF_FEF5;
+pub const DT_VERSYM: u64 = 0x6FFF_FFF0;
+pub const DT_RELACOUNT: u64 = 0x6FFF_FFF9;
+pub const DT_RELCOUNT: u64 = 0x6FFF_FFFA;
+pub const DT_FLAGS_1: u64 = 0x6FFF_FFFB;
+pub const DT_VERDEF: u64 = 0x6FFF_FFFC;
+pub const DT_VERDEFNUM: u64 = 0x6FFF_FFFD;
+pub const DT_VERNEED: u64 = 0x6FFF_FFFE;
+pub const DT_VERNEEDNUM: u64 = 0x6FFF_FFFF;
+pub const DT_HIOS: u64 = 0x6FFF_F000;
+pub const DT_LOPROC: u64 = 0x7000_0000;
+pub const DT_HIPROC: u64 = 0x7FFF_FFFF;
+
+pub const STB_LOCAL: u8 = 0;
+pub const STB_GLOBAL: u8 = 1;
+pub const STB_WEAK: u8 = 2;
+pub const STB_GNU_UNIQUE: u8 = 10;
+pub const STB_LOOS: u8 = 10;
+pub const STB_HIOS: u8 = 12;
+pub const STB_LOPROC: u8 = 13;
+pub const STB_HIPROC: u8 = 15;
+
+pub const STT_NOTYPE: u8 = 0;
+pub const STT_OBJECT: u8 = 1;
+pub const STT_FUNC: u8 = 2;
+pub const STT_SECTION: u8 = 3;
+pub const STT_FILE: u8 = 4;
+pub const STT_COMMON: u8 = 5;
+pub const STT_TLS: u8 = 6;
+pub const STT_GNU_IFUNC: u8 = 10;
+pub const STT_LOOS: u8 = 10;
+pub const STT_HIOS: u8 = 12;
+pub const STT_LOPROC: u8 = 13;
+pub const STT_HIPROC: u8 = 15;
+
+pub const STV_DEFAULT: u8 = 0;
+pub const STV_INTERNAL: u8 = 1;
+pub const STV_HIDDEN: u8 = 2;
+pub const STV_PROTECTED: u8 = 3;
+
+pub const NT_PRSTATUS: u32 = 1;
+
+pub const NT_GNU_ABI_TAG: u32 = 1;
+pub const NT_GNU_HWCAP: u32 = 2;
+pub const NT_GNU_BUILD_ID: u32 = 3;
+pub const NT_GNU_GOLD_VERSION: u32 = 4;
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xC000_0000;
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xC000_0002;
+pub const GNU_PROPERTY_X86_ISA_1_NEEDED: u32 = 0xC000_8002;
+
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: u32 = 0x1;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_PAC: u32 = 0x2;
+pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 0x1;
+pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 0x2;
+
+pub const DF_ORIGIN: u64 = 0x01;
+pub const DF_SYMBOLIC: u64 = 0x02;
+pub const DF_TEXTREL: u64 = 0x04;
+pub const DF_BIND_NOW: u64 = 0x08;
+pub const DF_STATIC_TLS: u64 = 0x10;
+
+pub const DF_1_NOW: u64 = 0x0000_0001;
+pub const DF_1_GLOBAL: u64 = 0x0000_0002;
+pub const DF_1_GROUP: u64 = 0x0000_0004;
+pub const DF_1_NODELETE: u64 = 0x0000_0008;
+pub const DF_1_LOADFLTR: u64 = 0x0000_0010;
+pub const DF_1_INITFIRST: u64 = 0x0000_0020;
+pub const DF_1_NOOPEN: u64 = 0x0000_0040;
+pub const DF_1_ORIGIN: u64 = 0x0000_0080;
+pub const DF_1_DIRECT: u64 = 0x0000_0100;
+pub const DF_1_INTERPOSE: u64 = 0x0000_0400;
+pub const DF_1_NODEFLIB: u64 = 0x0000_0800;
+pub const DF_1_NODUMP: u64 = 0x0000_1000;
+pub const DF_1_PIE: u64 = 0x0800_0000;
+
+pub const R_X86_64_NONE: u32 = 0;
+pub const R_X86_64_JUMP_SLOT: u32 = 7;
+pub const R_X86_64_RELATIVE: u32 = 8;
+
+pub const R_AARCH64_NONE: u32 = 0;
+pub const R_AARCH64_RELATIVE: u32 = 1027;
+
+pub const DW_EH_PE_ABSPTR: u8 = 0x00;
+pub const DW_EH_PE_ULEB128: u8 = 0x01;
+pub const DW_EH_PE_UDATA2: u8 = 0x02;
+pub const DW_EH_PE_UDATA4: u8 = 0x03;
+pub const DW_EH_PE_UDATA8: u8 = 0x04;
+pub const DW_EH_PE_SLEB128: u8 = 0x09;
+pub const DW_EH_PE_SDATA2: u8 = 0x0A;
+pub const DW_EH_PE_SDATA4: u8 = 0x0B;
+pub const DW_EH_PE_SDATA8: u8 = 0x0C;
+pub const DW_EH_PE_PCREL: u8 = 0x10;
+pub const DW_EH_PE_TEXTREL: u8 = 0x20;
+pub const DW_EH_PE_DATAREL: u8 = 0x30;
+pub const DW_EH_PE_FUNCREL: u8 = 0x40;
+pub const DW_EH_PE_ALIGNED: u8 = 0x50;
+pub const DW_EH_PE_INDIRECT: u8 = 0x80;
+pub const DW_EH_PE_OMIT: u8 = 0xFF;
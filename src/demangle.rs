@@ -0,0 +1,1083 @@
+// symbol names only need to be readable, so this covers the shapes compilers commonly emit for the
+// Itanium C++ ABI and Rust's legacy mangling. anything else is returned as it is
+pub fn demangle(name: &str) -> String {
+	demangle_rust(name).or_else(|| demangle_itanium(name)).unwrap_or_else(|| name.to_owned())
+}
+
+// legacy Rust names are Itanium nested names ending in a hash, e.g. `_ZN4core3ptr8drop17h0123..E`
+fn demangle_rust(name: &str) -> Option<String> {
+	let mut parser = Parser::new(name.strip_prefix("_ZN")?);
+
+	let mut path = Vec::new();
+	while !parser.eat(b'E') {
+		path.push(parser.source_name()?);
+	}
+	// kept like `nm` does, since it tells apart instances of generic functions
+	let hash = path.last()?;
+	let is_hash = hash.len() == 17
+		&& hash.starts_with('h')
+		&& hash[1..].bytes().all(|it| it.is_ascii_hexdigit());
+	if !parser.is_empty() || !is_hash || path.len() < 2 {
+		return None;
+	}
+
+	let path = path.iter().map(|it| unescape_rust(it)).collect::<Option<Vec<_>>>()?;
+	Some(path.join("::"))
+}
+
+// `$LT$` and friends stand in for characters that can't be in a symbol, `..` for `::`
+fn unescape_rust(ident: &str) -> Option<String> {
+	// identifiers can't start with `$`, so those get an underscore in front
+	let mut rest = ident.strip_prefix('_').filter(|it| it.starts_with('$')).unwrap_or(ident);
+	let mut unescaped = String::new();
+
+	while let Some(char) = rest.chars().next() {
+		if let Some(inner) = rest.strip_prefix('$') {
+			let end = inner.find('$')?;
+			let code = &inner[..end];
+			unescaped.push(match code {
+				"SP" => '@',
+				"BP" => '*',
+				"RF" => '&',
+				"LT" => '<',
+				"GT" => '>',
+				"LP" => '(',
+				"RP" => ')',
+				"C" => ',',
+				_ => char::from_u32(u32::from_str_radix(code.strip_prefix('u')?, 16).ok()?)?,
+			});
+			rest = &inner[end + 1..];
+		} else if let Some(inner) = rest.strip_prefix("..") {
+			unescaped.push_str("::");
+			rest = inner;
+		} else {
+			unescaped.push(char);
+			rest = &rest[char.len_utf8()..];
+		}
+	}
+
+	Some(unescaped)
+}
+
+fn demangle_itanium(name: &str) -> Option<String> {
+	let mut parser = Parser::new(name.strip_prefix("_Z")?);
+	let mut demangled = parser.encoding(true)?;
+
+	// GCC's clones of a function keep its name with a suffix, e.g. `.constprop.0` or `.cold`
+	let mut rest = parser.rest();
+	while !rest.is_empty() {
+		let suffix = rest.strip_prefix('.')?;
+		let mut end =
+			suffix.find(|it: char| !(it.is_ascii_lowercase() || it == '_')).unwrap_or(suffix.len());
+		if end == 0 {
+			end = suffix.find(|it: char| !it.is_ascii_digit()).unwrap_or(suffix.len());
+		}
+		while let Some(number) = suffix[end..].strip_prefix('.') {
+			let digits = number.find(|it: char| !it.is_ascii_digit()).unwrap_or(number.len());
+			if digits == 0 {
+				break;
+			}
+			end += 1 + digits;
+		}
+		if end == 0 {
+			return None;
+		}
+
+		demangled.push_str(&format!(" [clone .{}]", &suffix[..end]));
+		rest = &suffix[end..];
+	}
+
+	Some(demangled)
+}
+
+const MAX_DEPTH: usize = 256;
+const MAX_STEPS: usize = 100_000;
+
+// function types are kept with a marker where their declarator goes, e.g. the `*` in
+// `void (*)(int)`. `INNER_HOLE` is the marker once the parentheses are in place
+const HOLE: char = '\u{1}';
+const INNER_HOLE: char = '\u{2}';
+
+fn render(text: &str) -> String {
+	text.replace([HOLE, INNER_HOLE], "")
+}
+
+// references to references collapse, to `&&` only if both are
+fn add_declarator(text: &str, suffix: &str) -> String {
+	match (suffix, text.strip_suffix('&')) {
+		("&&", Some(_)) => text.to_owned(),
+		("&", Some(inner)) => format!("{}&", inner.strip_suffix('&').unwrap_or(inner)),
+		_ => format!("{text}{suffix}"),
+	}
+}
+
+struct Name {
+	text: String,
+	// template functions mangle their return type, everything else doesn't
+	has_return_type: bool,
+	qualifiers: String,
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	position: usize,
+	substitutions: Vec<Substitution>,
+	template_args: Vec<Arg>,
+	// which template arguments are in use, bumped whenever they change
+	scope: usize,
+	scopes: usize,
+	// counts template parameter uses, to tell which substitutions depend on them
+	param_uses: usize,
+	// set when a template parameter resolved to a pack, for `Dp` to expand
+	pack_size: Option<usize>,
+	// the element of the pack `Dp` is expanding
+	pack_index: Option<usize>,
+	depth: usize,
+	steps: usize,
+}
+
+// substitutions are kept as text, so ones using template parameters are only valid in the scope
+// they were made in. c++filt resolves those again where they're used
+struct Substitution {
+	text: String,
+	scope: Option<usize>,
+}
+
+#[derive(Clone)]
+enum Arg {
+	Single(String),
+	Pack(Vec<String>),
+}
+
+impl Arg {
+	fn text(&self) -> String {
+		match self {
+			Arg::Single(it) => it.clone(),
+			Arg::Pack(it) => it.join(", "),
+		}
+	}
+
+	fn rendered(&self) -> String {
+		render(&self.text())
+	}
+}
+
+impl<'a> Parser<'a> {
+	fn new(input: &'a str) -> Self {
+		Parser {
+			input,
+			position: 0,
+			substitutions: Vec::new(),
+			template_args: Vec::new(),
+			scope: 0,
+			scopes: 0,
+			param_uses: 0,
+			pack_size: None,
+			pack_index: None,
+			depth: 0,
+			steps: 0,
+		}
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.input[self.position..]
+	}
+
+	fn is_empty(&self) -> bool {
+		self.position == self.input.len()
+	}
+
+	fn peek(&self) -> Option<u8> {
+		self.input.as_bytes().get(self.position).copied()
+	}
+
+	fn peek_next(&self) -> Option<u8> {
+		self.input.as_bytes().get(self.position + 1).copied()
+	}
+
+	fn eat(&mut self, byte: u8) -> bool {
+		let matches = self.peek() == Some(byte);
+		if matches {
+			self.position += 1;
+		}
+		matches
+	}
+
+	fn eat_str(&mut self, prefix: &str) -> bool {
+		let matches = self.rest().starts_with(prefix);
+		if matches {
+			self.position += prefix.len();
+		}
+		matches
+	}
+
+	fn expect(&mut self, byte: u8) -> Option<()> {
+		self.eat(byte).then_some(())
+	}
+
+	fn number(&mut self) -> Option<usize> {
+		let digits = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+		let number = self.rest()[..digits].parse().ok()?;
+		self.position += digits;
+		Some(number)
+	}
+
+	// `<digits>_` counts from 1, `_` alone is 0
+	fn index(&mut self, radix: u32) -> Option<usize> {
+		if self.eat(b'_') {
+			return Some(0);
+		}
+		let end = self.rest().find('_')?;
+		let index = usize::from_str_radix(&self.rest()[..end], radix).ok()?;
+		self.position += end + 1;
+		Some(index + 1)
+	}
+
+	// names come from files, so a deeply nested one shouldn't run out of stack, nor should pack
+	// expansions in pack expansions take forever
+	fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+		if self.depth == MAX_DEPTH || self.steps == MAX_STEPS {
+			return None;
+		}
+		self.depth += 1;
+		self.steps += 1;
+		let parsed = parse(self);
+		self.depth -= 1;
+		parsed
+	}
+
+	// made since `param_uses` was `since`
+	fn push_substitution(&mut self, text: String, since: usize) {
+		let scope = (self.param_uses != since).then_some(self.scope);
+		self.substitutions.push(Substitution { text, scope });
+	}
+
+	fn source_name(&mut self) -> Option<String> {
+		let size = self.number()?;
+		let name = self.rest().get(..size)?;
+		self.position += size;
+		Some(name.to_owned())
+	}
+
+	// functions of local names are printed without their return type, like c++filt does
+	fn encoding(&mut self, with_return_type: bool) -> Option<String> {
+		self.nested(|it| it.parse_encoding(with_return_type))
+	}
+
+	fn parse_encoding(&mut self, with_return_type: bool) -> Option<String> {
+		for (prefix, description) in [
+			("TV", "vtable for "),
+			("TT", "VTT for "),
+			("TI", "typeinfo for "),
+			("TS", "typeinfo name for "),
+		] {
+			if self.eat_str(prefix) {
+				return Some(format!("{description}{}", render(&self.type_()?)));
+			}
+		}
+		for (prefix, description) in [
+			("GV", "guard variable for "),
+			("TH", "TLS init function for "),
+			("TW", "TLS wrapper function for "),
+		] {
+			if self.eat_str(prefix) {
+				return Some(format!("{description}{}", self.name(false)?.text));
+			}
+		}
+		for (prefix, description) in [
+			("GTt", "transaction clone for "),
+			("GTn", "non-transaction clone for "),
+		] {
+			if self.eat_str(prefix) {
+				return Some(format!("{description}{}", self.encoding(true)?));
+			}
+		}
+		// thunks adjust `this`, or the returned pointer for covariant ones, before calling the
+		// function. the adjustments aren't shown
+		if self.eat_str("Tc") {
+			self.call_offset()?;
+			self.call_offset()?;
+			return Some(format!("covariant return thunk to {}", self.encoding(true)?));
+		}
+		if self.peek() == Some(b'T') && matches!(self.peek_next(), Some(b'h' | b'v')) {
+			self.position += 1;
+			let description = if self.call_offset()? {
+				"virtual thunk to "
+			} else {
+				"non-virtual thunk to "
+			};
+			return Some(format!("{description}{}", self.encoding(true)?));
+		}
+
+		let name = self.name(true)?;
+		if self.is_empty() || matches!(self.peek(), Some(b'E' | b'.')) {
+			return Some(name.text);
+		}
+
+		let return_type = if name.has_return_type {
+			format!("{} ", render(&self.type_()?))
+		} else {
+			String::new()
+		};
+		let return_type = if with_return_type {
+			return_type.as_str()
+		} else {
+			""
+		};
+		let mut parameters = Vec::new();
+		while !self.is_empty() && !matches!(self.peek(), Some(b'E' | b'.')) {
+			parameters.push(render(&self.type_()?));
+		}
+		parameters.retain(|it| !it.is_empty());
+		if parameters == ["void"] {
+			parameters.clear();
+		}
+
+		Some(format!("{return_type}{}({}){}", name.text, parameters.join(", "), name.qualifiers))
+	}
+
+	// `h <offset> _` or `v <offset> _ <vcall offset> _`, true for the virtual one
+	fn call_offset(&mut self) -> Option<bool> {
+		let is_virtual = match self.peek()? {
+			b'h' => false,
+			b'v' => true,
+			_ => return None,
+		};
+		self.position += 1;
+		for _ in 0..1 + is_virtual as usize {
+			self.eat(b'n');
+			self.number()?;
+			self.expect(b'_')?;
+		}
+		Some(is_virtual)
+	}
+
+	// `record` marks the name of the function being demangled, whose template arguments are the
+	// ones `T_` refers to
+	fn name(&mut self, record: bool) -> Option<Name> {
+		match self.peek()? {
+			b'N' => self.nested_name(record),
+			b'Z' => {
+				self.position += 1;
+				// the function's template arguments only apply inside of it
+				let (template_args, scope) = (self.template_args.clone(), self.scope);
+				let function = self.encoding(false)?;
+				if !record {
+					self.template_args = template_args;
+					self.scope = scope;
+				}
+				self.expect(b'E')?;
+				let mut entity = if self.eat(b's') {
+					Name {
+						text: "string literal".to_owned(),
+						has_return_type: false,
+						qualifiers: String::new(),
+					}
+				} else {
+					self.name(record)?
+				};
+				// discriminators tell apart entities of the same name in one function
+				if self.eat(b'_') {
+					if self.eat(b'_') {
+						self.number()?;
+						self.expect(b'_')?;
+					} else {
+						self.number()?;
+					}
+				}
+
+				entity.text = format!("{function}::{}", entity.text);
+				Some(entity)
+			}
+			_ => {
+				let since = self.param_uses;
+				let is_substitution = self.peek() == Some(b'S') && self.peek_next() != Some(b't');
+				let mut text = if is_substitution {
+					self.substitution()?.0
+				} else {
+					let is_std = self.eat_str("St");
+					self.eat(b'L');
+					let name = self.unqualified_name()?.0;
+					if is_std {
+						format!("std::{name}")
+					} else {
+						name
+					}
+				};
+
+				let has_return_type = self.peek() == Some(b'I');
+				if has_return_type {
+					if !is_substitution {
+						self.push_substitution(text.clone(), since);
+					}
+					self.push_template_args(&mut text, record)?;
+				}
+				Some(Name {
+					text,
+					has_return_type,
+					qualifiers: String::new(),
+				})
+			}
+		}
+	}
+
+	fn nested_name(&mut self, record: bool) -> Option<Name> {
+		self.expect(b'N')?;
+
+		let mut qualifiers = String::new();
+		for (byte, qualifier) in [(b'r', " restrict"), (b'V', " volatile"), (b'K', " const")] {
+			if self.eat(byte) {
+				qualifiers.insert_str(0, qualifier);
+			}
+		}
+		if self.eat(b'R') {
+			qualifiers.push_str(" &");
+		} else if self.eat(b'O') {
+			qualifiers.push_str(" &&");
+		}
+
+		let since = self.param_uses;
+		let mut prefix = String::new();
+		// the unqualified name constructors and destructors are named after
+		let mut last_name = String::new();
+		let mut has_return_type = false;
+		let mut is_special = false;
+
+		while !self.eat(b'E') {
+			let join = |prefix: &str, name: &str| {
+				if prefix.is_empty() {
+					name.to_owned()
+				} else {
+					format!("{prefix}::{name}")
+				}
+			};
+
+			has_return_type = false;
+			match (self.peek()?, self.peek_next()) {
+				(b'S', Some(b't')) => {
+					self.position += 2;
+					prefix = "std".to_owned();
+					continue;
+				}
+				(b'S', _) => {
+					let (text, name) = self.substitution()?;
+					prefix = text;
+					last_name = name;
+					continue;
+				}
+				(b'I', _) => {
+					self.push_template_args(&mut prefix, record)?;
+					has_return_type = true;
+				}
+				(b'T', _) => {
+					prefix = self.template_param()?;
+				}
+				(b'C', Some(b'1'..=b'5')) | (b'D', Some(b'0'..=b'5')) => {
+					let is_constructor = self.peek() == Some(b'C');
+					self.position += 2;
+					let name = if is_constructor {
+						last_name.clone()
+					} else {
+						format!("~{last_name}")
+					};
+					prefix = join(&prefix, &name);
+					is_special = true;
+				}
+				_ => {
+					let (name, is_conversion) = self.unqualified_name()?;
+					prefix = join(&prefix, &name);
+					// without the ABI tags, which constructors don't repeat
+					last_name = name.split("[abi:").next().unwrap_or_default().to_owned();
+					is_special = is_conversion;
+				}
+			}
+
+			if self.peek() != Some(b'E') {
+				self.push_substitution(prefix.clone(), since);
+			}
+		}
+
+		Some(Name {
+			text: prefix,
+			has_return_type: has_return_type && !is_special,
+			qualifiers,
+		})
+	}
+
+	// also tells whether the name is a conversion operator, which has no return type to mangle
+	fn unqualified_name(&mut self) -> Option<(String, bool)> {
+		let mut is_conversion = false;
+		let mut name = match self.peek()? {
+			b'0'..=b'9' => {
+				let name = self.source_name()?;
+				if name.starts_with("_GLOBAL__N") {
+					"(anonymous namespace)".to_owned()
+				} else {
+					name
+				}
+			}
+			b'U' => self.unnamed_type()?,
+			b'c' if self.peek_next() == Some(b'v') => {
+				self.position += 2;
+				is_conversion = true;
+				format!("operator {}", render(&self.type_()?))
+			}
+			b'a'..=b'z' => {
+				let code = self.rest().get(..2)?;
+				let operator = OPERATORS.iter().find(|(it, _)| *it == code)?.1;
+				self.position += 2;
+				let space = if operator.starts_with(char::is_alphabetic) {
+					" "
+				} else {
+					""
+				};
+				format!("operator{space}{operator}")
+			}
+			_ => return None,
+		};
+
+		while self.eat(b'B') {
+			name.push_str(&format!("[abi:{}]", self.source_name()?));
+		}
+		Some((name, is_conversion))
+	}
+
+	fn unnamed_type(&mut self) -> Option<String> {
+		let name = if self.eat_str("Ul") {
+			let mut parameters = Vec::new();
+			while !self.eat(b'E') {
+				parameters.push(render(&self.type_()?));
+			}
+			if parameters == ["void"] {
+				parameters.clear();
+			}
+			format!("{{lambda({})#", parameters.join(", "))
+		} else if self.eat_str("Ut") {
+			"{unnamed type#".to_owned()
+		} else {
+			return None;
+		};
+
+		Some(format!("{name}{}}}", self.index(10)? + 1))
+	}
+
+	// `operator<` needs a space before its arguments to stay readable
+	fn push_template_args(&mut self, text: &mut String, record: bool) -> Option<()> {
+		let args = self.template_args(record)?;
+		if text.ends_with('<') {
+			text.push(' ');
+		}
+		text.push_str(&args);
+		Some(())
+	}
+
+	fn template_args(&mut self, record: bool) -> Option<String> {
+		self.expect(b'I')?;
+
+		let mut args = Vec::new();
+		while !self.eat(b'E') {
+			if self.eat(b'J') {
+				let mut pack = Vec::new();
+				while !self.eat(b'E') {
+					pack.push(self.template_arg()?);
+				}
+				args.push(Arg::Pack(pack));
+			} else {
+				args.push(Arg::Single(self.template_arg()?));
+			}
+		}
+
+		let mut text = format!(
+			"<{}",
+			args.iter()
+				.map(Arg::rendered)
+				.filter(|it| !it.is_empty())
+				.collect::<Vec<_>>()
+				.join(", ")
+		);
+		// like c++filt, `> >` stays apart unless an empty pack was in between
+		if args.last().is_some_and(|it| it.rendered().ends_with('>')) {
+			text.push(' ');
+		}
+		text.push('>');
+
+		if record {
+			self.template_args = args;
+			self.scopes += 1;
+			self.scope = self.scopes;
+		}
+		Some(text)
+	}
+
+	fn template_arg(&mut self) -> Option<String> {
+		if !self.eat(b'L') {
+			return self.type_();
+		}
+
+		// literals, e.g. `Li5E` for `5`, enum values are cast to their type
+		if matches!(self.peek()?, b'N' | b'S' | b'0'..=b'9') {
+			let kind = render(&self.type_()?);
+			let sign = if self.eat(b'n') { "-" } else { "" };
+			let value = self.number()?;
+			self.expect(b'E')?;
+			return Some(format!("({kind}){sign}{value}"));
+		}
+		let kind = self.peek()?;
+		self.position += 1;
+		let sign = if self.eat(b'n') { "-" } else { "" };
+		let value = self.number()?;
+		self.expect(b'E')?;
+
+		Some(match kind {
+			b'b' if value == 0 => "false".to_owned(),
+			b'b' if value == 1 => "true".to_owned(),
+			b'i' => format!("{sign}{value}"),
+			b'j' => format!("{sign}{value}u"),
+			b'l' => format!("{sign}{value}l"),
+			b'm' => format!("{sign}{value}ul"),
+			b'x' => format!("{sign}{value}ll"),
+			b'y' => format!("{sign}{value}ull"),
+			_ => {
+				let kind = BUILTIN_TYPES.iter().find(|(it, _)| *it == kind)?.1;
+				format!("({kind}){sign}{value}")
+			}
+		})
+	}
+
+	fn template_param(&mut self) -> Option<String> {
+		self.expect(b'T')?;
+		let index = self.index(10)?;
+		self.param_uses += 1;
+		let arg = self.template_args.get(index)?;
+		if let Arg::Pack(pack) = arg {
+			self.pack_size = Some(pack.len());
+			if let Some(index) = self.pack_index {
+				return pack.get(index).cloned();
+			}
+		}
+		Some(arg.text())
+	}
+
+	// the text, and the unqualified name constructors and destructors of it are named after
+	fn substitution(&mut self) -> Option<(String, String)> {
+		self.expect(b'S')?;
+
+		let name = |text: &str| {
+			let text = text.split('<').next().unwrap_or_default();
+			text.rsplit("::").next().unwrap_or_default().to_owned()
+		};
+
+		let standard = match self.peek()? {
+			b'a' => Some("std::allocator"),
+			b'b' => Some("std::basic_string"),
+			b's' => Some("std::basic_string<char, std::char_traits<char>, std::allocator<char> >"),
+			b'i' => Some("std::basic_istream<char, std::char_traits<char> >"),
+			b'o' => Some("std::basic_ostream<char, std::char_traits<char> >"),
+			b'd' => Some("std::basic_iostream<char, std::char_traits<char> >"),
+			_ => None,
+		};
+		if let Some(text) = standard {
+			self.position += 1;
+			return Some((text.to_owned(), name(text)));
+		}
+
+		if !matches!(self.peek()?, b'_' | b'0'..=b'9' | b'A'..=b'Z') {
+			return None;
+		}
+		let index = self.index(36)?;
+		let substitution = self.substitutions.get(index)?;
+		if substitution.scope.is_some_and(|it| it != self.scope) {
+			return None;
+		}
+		let text = substitution.text.clone();
+		let name = name(&text);
+		Some((text, name))
+	}
+
+	// the pattern is parsed again for every element of the pack it uses
+	fn pack_expansion(&mut self) -> Option<String> {
+		let start = self.position;
+		let substitutions = self.substitutions.len();
+		let since = self.param_uses;
+
+		self.pack_size = None;
+		let text = self.type_()?;
+		let size = self.pack_size.take()?;
+		if size <= 1 {
+			return Some(if size == 0 {
+				String::new()
+			} else {
+				render(&text)
+			});
+		}
+
+		let mut expanded = Vec::new();
+		for index in 0..size {
+			self.position = start;
+			self.substitutions.truncate(substitutions);
+			self.pack_index = Some(index);
+			let text = self.type_();
+			self.pack_index = None;
+			expanded.push(render(&text?));
+		}
+		// the substitutions made along the way only fit the last element
+		if self.param_uses != since {
+			for substitution in &mut self.substitutions[substitutions..] {
+				if substitution.scope.is_some() {
+					substitution.scope = Some(usize::MAX);
+				}
+			}
+		}
+
+		Some(expanded.join(", "))
+	}
+
+	fn type_(&mut self) -> Option<String> {
+		self.nested(Self::parse_type)
+	}
+
+	fn parse_type(&mut self) -> Option<String> {
+		let since = self.param_uses;
+		let byte = self.peek()?;
+		if let Some((_, builtin)) = BUILTIN_TYPES.iter().find(|(it, _)| *it == byte) {
+			self.position += 1;
+			return Some((*builtin).to_owned());
+		}
+
+		let text = match byte {
+			b'r' | b'V' | b'K' => {
+				let mut qualifiers = Vec::new();
+				for (byte, qualifier) in
+					[(b'r', " restrict"), (b'V', " volatile"), (b'K', " const")]
+				{
+					if self.eat(byte) {
+						qualifiers.insert(0, qualifier);
+					}
+				}
+				// a template parameter can already be qualified
+				let mut text = self.type_()?;
+				if text.contains(HOLE) {
+					return None;
+				}
+				for qualifier in qualifiers {
+					if text.contains(INNER_HOLE) {
+						text = text.replace(INNER_HOLE, &format!("{qualifier}{INNER_HOLE}"));
+					} else if !text.ends_with(qualifier) {
+						text.push_str(qualifier);
+					}
+				}
+				text
+			}
+			b'P' | b'R' | b'O' => {
+				self.position += 1;
+				let suffix = match byte {
+					b'P' => "*",
+					b'R' => "&",
+					_ => "&&",
+				};
+				let text = self.type_()?;
+				if text.contains(HOLE) {
+					text.replace(HOLE, &format!("({suffix}{INNER_HOLE})"))
+				} else if let Some((declarator, rest)) = text.split_once(INNER_HOLE) {
+					format!("{}{INNER_HOLE}{rest}", add_declarator(declarator, suffix))
+				} else {
+					add_declarator(&text, suffix)
+				}
+			}
+			b'F' => {
+				self.position += 1;
+				self.eat(b'Y');
+				let return_type = self.type_()?;
+				if return_type.contains([HOLE, INNER_HOLE]) {
+					return None;
+				}
+
+				let mut parameters = Vec::new();
+				// member functions can be ref-qualified
+				let mut reference = "";
+				while !self.eat(b'E') {
+					if self.eat_str("RE") {
+						reference = " &";
+						break;
+					}
+					if self.eat_str("OE") {
+						reference = " &&";
+						break;
+					}
+					parameters.push(render(&self.type_()?));
+				}
+				if parameters == ["void"] {
+					parameters.clear();
+				}
+				format!("{return_type} {HOLE}({}){reference}", parameters.join(", "))
+			}
+			b'M' => {
+				self.position += 1;
+				let class = render(&self.type_()?);
+
+				// the qualifiers of a member function go after its parameters
+				let start = self.position;
+				let mut qualifiers = String::new();
+				for (byte, qualifier) in
+					[(b'r', " restrict"), (b'V', " volatile"), (b'K', " const")]
+				{
+					if self.eat(byte) {
+						qualifiers.insert_str(0, qualifier);
+					}
+				}
+				let member = if !qualifiers.is_empty() && self.peek() == Some(b'F') {
+					// and only the qualified function type can be substituted
+					let member = format!("{}{qualifiers}", self.type_()?);
+					self.substitutions.pop();
+					self.push_substitution(member.clone(), since);
+					member
+				} else {
+					self.position = start;
+					self.type_()?
+				};
+
+				if member.contains(HOLE) {
+					member.replace(HOLE, &format!("({class}::*{INNER_HOLE})"))
+				} else if let Some((declarator, rest)) = member.split_once(INNER_HOLE) {
+					format!("{declarator} {class}::*{INNER_HOLE}{rest}")
+				} else {
+					format!("{member} {class}::*")
+				}
+			}
+			b'u' => {
+				self.position += 1;
+				self.source_name()?
+			}
+			b'D' => {
+				let text = match self.peek_next()? {
+					b'a' => "auto",
+					b'c' => "decltype(auto)",
+					b'd' => "decimal64",
+					b'e' => "decimal128",
+					b'f' => "decimal32",
+					b'h' => "half",
+					b'i' => "char32_t",
+					b's' => "char16_t",
+					b'u' => "char8_t",
+					b'n' => "decltype(nullptr)",
+					b'p' => {
+						self.position += 2;
+						return self.pack_expansion();
+					}
+					_ => return None,
+				};
+				self.position += 2;
+				return Some(text.to_owned());
+			}
+			b'S' if self.peek_next() != Some(b't') => {
+				let mut text = self.substitution()?.0;
+				if self.peek() != Some(b'I') {
+					return Some(text);
+				}
+				self.push_template_args(&mut text, false)?;
+				text
+			}
+			b'T' => {
+				let mut text = self.template_param()?;
+				self.push_substitution(text.clone(), since);
+				if self.peek() != Some(b'I') {
+					return Some(text);
+				}
+				self.push_template_args(&mut text, false)?;
+				text
+			}
+			b'N' | b'Z' | b'S' | b'0'..=b'9' => self.name(false)?.text,
+			_ => return None,
+		};
+
+		self.push_substitution(text.clone(), since);
+		Some(text)
+	}
+}
+
+const BUILTIN_TYPES: &[(u8, &str)] = &[
+	(b'v', "void"),
+	(b'w', "wchar_t"),
+	(b'b', "bool"),
+	(b'c', "char"),
+	(b'a', "signed char"),
+	(b'h', "unsigned char"),
+	(b's', "short"),
+	(b't', "unsigned short"),
+	(b'i', "int"),
+	(b'j', "unsigned int"),
+	(b'l', "long"),
+	(b'm', "unsigned long"),
+	(b'x', "long long"),
+	(b'y', "unsigned long long"),
+	(b'n', "__int128"),
+	(b'o', "unsigned __int128"),
+	(b'f', "float"),
+	(b'd', "double"),
+	(b'e', "long double"),
+	(b'g', "__float128"),
+	(b'z', "..."),
+];
+
+const OPERATORS: &[(&str, &str)] = &[
+	("nw", "new"),
+	("na", "new[]"),
+	("dl", "delete"),
+	("da", "delete[]"),
+	("ps", "+"),
+	("ng", "-"),
+	("ad", "&"),
+	("de", "*"),
+	("co", "~"),
+	("pl", "+"),
+	("mi", "-"),
+	("ml", "*"),
+	("dv", "/"),
+	("rm", "%"),
+	("an", "&"),
+	("or", "|"),
+	("eo", "^"),
+	("aS", "="),
+	("pL", "+="),
+	("mI", "-="),
+	("mL", "*="),
+	("dV", "/="),
+	("rM", "%="),
+	("aN", "&="),
+	("oR", "|="),
+	("eO", "^="),
+	("ls", "<<"),
+	("rs", ">>"),
+	("lS", "<<="),
+	("rS", ">>="),
+	("eq", "=="),
+	("ne", "!="),
+	("lt", "<"),
+	("gt", ">"),
+	("le", "<="),
+	("ge", ">="),
+	("ss", "<=>"),
+	("nt", "!"),
+	("aa", "&&"),
+	("oo", "||"),
+	("pp", "++"),
+	("mm", "--"),
+	("cm", ","),
+	("pm", "->*"),
+	("pt", "->"),
+	("cl", "()"),
+	("ix", "[]"),
+	("qu", "?"),
+];
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// each checked against `c++filt`
+	#[test]
+	fn itanium_names_are_demangled() {
+		let cases = [
+			("_ZNSt6vectorIiSaIiEE9push_backEOi", "std::vector<int, std::allocator<int> >::push_back(int&&)"),
+			(
+				"_ZSt3maxImERKT_S2_S2_",
+				"unsigned long const& std::max<unsigned long>(unsigned long const&, unsigned long const&)",
+			),
+			("_ZZ11lambda_uservENKUliE_clEi", "lambda_user()::{lambda(int)#1}::operator()(int) const"),
+			("_ZNSolsEi", "std::basic_ostream<char, std::char_traits<char> >::operator<<(int)"),
+			("_Z5tableB5cxx11", "table[abi:cxx11]"),
+			("_ZN5outer12_GLOBAL__N_16hiddenEi", "outer::(anonymous namespace)::hidden(int)"),
+			("_Z8registerPFviEPFPKcvE", "register(void (*)(int), char const* (*)())"),
+			("_ZN3FooC2ERKS_", "Foo::Foo(Foo const&)"),
+			("_ZN3FooD0Ev", "Foo::~Foo()"),
+			("_Z3addii.constprop.0", "add(int, int) [clone .constprop.0]"),
+			("_ZTV3Foo", "vtable for Foo"),
+			("_ZNKSt8functionIFvvEEclEv", "std::function<void ()>::operator()() const"),
+			("_Z3fooIJicEEvDpT_", "void foo<int, char>(int, char)"),
+		];
+		for (name, expected) in cases {
+			assert_eq!(demangle(name), expected, "{name}");
+		}
+	}
+
+	#[test]
+	fn thunks_and_clones_name_their_function() {
+		let cases = [
+			(
+				"_ZTv0_n24_NSdD0Ev",
+				"virtual thunk to std::basic_iostream<char, std::char_traits<char> >::~basic_iostream()",
+			),
+			("_ZThn16_N3Foo3barEv", "non-virtual thunk to Foo::bar()"),
+			("_ZTch0_h16_N3Foo5cloneEv", "covariant return thunk to Foo::clone()"),
+			("_ZTcv0_n24_v0_n32_N3Foo5cloneEv", "covariant return thunk to Foo::clone()"),
+			(
+				"_ZGTtNSt11logic_errorC1EPKc",
+				"transaction clone for std::logic_error::logic_error(char const*)",
+			),
+			("_ZGTtNKSt9exception4whatEv", "transaction clone for std::exception::what() const"),
+			("_ZGTn3foov", "non-transaction clone for foo()"),
+		];
+		for (name, expected) in cases {
+			assert_eq!(demangle(name), expected, "{name}");
+		}
+	}
+
+	#[test]
+	fn decimal_floats_are_builtin_types() {
+		assert_eq!(demangle("_ZTIDd"), "typeinfo for decimal64");
+		assert_eq!(demangle("_ZTIDe"), "typeinfo for decimal128");
+		assert_eq!(demangle("_ZTIDf"), "typeinfo for decimal32");
+		assert_eq!(demangle("_ZTIPKDd"), "typeinfo for decimal64 const*");
+		assert_eq!(demangle("_Z1fDdDeDfDh"), "f(decimal64, decimal128, decimal32, half)");
+	}
+
+	#[test]
+	fn pointers_to_members_name_their_class() {
+		let cases = [
+			(
+				"_ZNSt15__exception_ptr13exception_ptrC1EMS0_FvvE",
+				"std::__exception_ptr::exception_ptr::exception_ptr(void (std::__exception_ptr::exception_ptr::*)())",
+			),
+			("_Z1fM3Fooi", "f(int Foo::*)"),
+			("_Z1fM3FooKi", "f(int const Foo::*)"),
+			("_Z1fM3FooPFivE", "f(int (* Foo::*)())"),
+			("_Z1fPM3FooFivE", "f(int (Foo::**)())"),
+			("_Z1fRM3FooFivE", "f(int (Foo::*&)())"),
+			("_Z1fM3FooVKFivE", "f(int (Foo::*)() const volatile)"),
+			("_Z1fM3FooFivRE", "f(int (Foo::*)() &)"),
+			("_Z1fM3FooFivOE", "f(int (Foo::*)() &&)"),
+			// only the qualified function type is a substitution candidate
+			("_Z1fM3FooKFivES0_", "f(int (Foo::*)() const, int () const)"),
+			("_Z1fM3FooFivES1_", "f(int (Foo::*)(), int (Foo::*)())"),
+		];
+		for (name, expected) in cases {
+			assert_eq!(demangle(name), expected, "{name}");
+		}
+	}
+
+	#[test]
+	fn rust_names_keep_their_hash() {
+		assert_eq!(
+			demangle("_ZN4core3ptr13drop_in_place17h0123456789abcdefE"),
+			"core::ptr::drop_in_place::h0123456789abcdef"
+		);
+		assert_eq!(
+			demangle("_ZN37$LT$T$u20$as$u20$core..fmt..Debug$GT$3fmt17h0123456789abcdefE"),
+			"<T as core::fmt::Debug>::fmt::h0123456789abcdef"
+		);
+	}
+
+	#[test]
+	fn other_names_are_returned_as_they_are() {
+		for name in [
+			"main",
+			"_Zfoo",
+			"_ZN3Foo",
+			"_Z3addii.",
+			"_GLOBAL_OFFSET_TABLE_",
+			"",
+		] {
+			assert_eq!(demangle(name), name);
+		}
+	}
+}
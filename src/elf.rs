@@ -1,16 +1,79 @@
+mod abi;
+mod alignment;
+mod consistency;
+mod coredump;
+mod diff;
+mod disasm;
+mod dynamic;
+mod edit;
+mod eh_frame;
+mod flags;
+mod go;
+mod hash;
+mod mips;
+mod note;
+#[cfg(feature = "object-compat")]
+mod object_compat;
+mod plt;
+mod reconstruct;
+mod relocation;
+mod security;
+mod strings;
+mod symbol;
+mod usdt;
+mod version;
+mod write;
+
 use std::{
+	borrow::Cow,
+	cell::{OnceCell, RefCell},
+	fmt,
 	fs::File,
 	io::{self, BufReader, Read, Seek, SeekFrom},
+	ops::{ControlFlow, Range},
 };
 
-use crate::error::Error;
+pub use abi::{AbiChange, AbiDiff, AbiSymbol};
+pub use alignment::AlignmentIssue;
+pub use coredump::CoreDump;
+pub use diff::{ElfDiff, HeaderChange, SectionResize};
+pub use disasm::DisasmContext;
+pub use dynamic::{DynamicEntry, DynamicFlags, DynamicTag, LibcFlavor};
+pub use edit::StripOptions;
+pub use eh_frame::FunctionRange;
+pub use flags::{ArmFlags, HeaderFlags, MipsAbi, MipsFlags, SectionFlags};
+pub use go::GoBuildInfo;
+pub use mips::MipsAbiFlags;
+pub use note::{GnuProperty, Note, NoteIter};
+pub use plt::PltEntry;
+pub use relocation::{Rela, Relocation};
+pub use security::{Relro, SecurityFeatures};
+pub use strings::StringHit;
+pub use symbol::{Symbol, SymbolBinding, SymbolStats, SymbolType, SymbolVisibility};
+pub use usdt::UsdtProbe;
+pub use version::VersionDef;
+
+use crate::{
+	consts::{
+		ELFCLASS32, ELFCLASS64, ELFDATA2LSB, ELFDATA2MSB, ELFMAG, PF_X, PN_XNUM, SHN_UNDEF,
+		SHN_XINDEX, SHT_MIPS_ABIFLAGS,
+	},
+	error::{Error, ErrorKind},
+	reader::Reader,
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
 pub struct Elf {
 	pub header: FileHeader,
-	pub segments: Vec<Segment>,
-	pub sections: Vec<Section>,
+	// read-only outside the crate, edits go through methods that keep the headers consistent
+	pub(crate) segments: Vec<Segment>,
+	pub(crate) sections: Vec<Section>,
+	pub warnings: Vec<Warning>,
+	pub file_size: u64,
+	reader: RefCell<ElfFile>,
+	// parsed once for lookups that hand out references, edits to the bodies reset it
+	symbols: OnceCell<Vec<Symbol>>,
 }
 
 pub struct FileHeader {
@@ -37,15 +100,24 @@ pub struct Ident {
 	pub version: u8,
 	pub os_abi: OsAbi,
 	pub abi_version: u8,
+	// unused by the spec, but kept since it's a place to hide data in
+	pub padding: [u8; 7],
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum OsAbi {
 	SystemV,
 	Linux,
 	Other(u8),
 }
 
+impl OsAbi {
+	// the named variants, leaving out the catch-all ranges
+	pub fn all_known() -> &'static [Self] {
+		&[Self::SystemV, Self::Linux]
+	}
+}
+
 impl From<u8> for OsAbi {
 	fn from(value: u8) -> Self {
 		match value {
@@ -66,7 +138,7 @@ impl From<OsAbi> for u8 {
 	}
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum FileType {
 	None,
 	Relocatable,
@@ -78,6 +150,18 @@ pub enum FileType {
 	Other(u16),
 }
 
+impl FileType {
+	pub fn all_known() -> &'static [Self] {
+		&[
+			Self::None,
+			Self::Relocatable,
+			Self::Executable,
+			Self::Dynamic,
+			Self::Core,
+		]
+	}
+}
+
 impl From<u16> for FileType {
 	fn from(value: u16) -> Self {
 		match value {
@@ -108,22 +192,39 @@ impl From<FileType> for u16 {
 	}
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Machine {
 	X86,
+	Mips,
 	Arm,
 	Amd64,
 	Arm64,
+	RiscV,
 	Other(u16),
 }
 
+impl Machine {
+	pub fn all_known() -> &'static [Self] {
+		&[
+			Self::X86,
+			Self::Mips,
+			Self::Arm,
+			Self::Amd64,
+			Self::Arm64,
+			Self::RiscV,
+		]
+	}
+}
+
 impl From<u16> for Machine {
 	fn from(value: u16) -> Self {
 		match value {
 			0x03 => Self::X86,
+			0x08 => Self::Mips,
 			0x28 => Self::Arm,
 			0x3E => Self::Amd64,
 			0xB7 => Self::Arm64,
+			0xF3 => Self::RiscV,
 			_ => Self::Other(value),
 		}
 	}
@@ -133,9 +234,11 @@ impl From<Machine> for u16 {
 	fn from(machine: Machine) -> Self {
 		match machine {
 			Machine::X86 => 0x03,
+			Machine::Mips => 0x08,
 			Machine::Arm => 0x28,
 			Machine::Amd64 => 0x3E,
 			Machine::Arm64 => 0xB7,
+			Machine::RiscV => 0xF3,
 			Machine::Other(value) => value,
 		}
 	}
@@ -150,10 +253,56 @@ pub struct Segment {
 	pub file_size: u64,
 	pub memory_size: u64,
 	pub alignment: u64,
-	pub data: Vec<u8>,
+	// kept private so bodies can't be swapped out from under the headers. filled in on first use,
+	// see `Elf::segment_data`
+	pub(crate) data: OnceCell<Vec<u8>>,
 }
 
-#[derive(Debug, PartialEq)]
+impl Segment {
+	// `None` until the body has been read
+	pub fn data(&self) -> Option<&[u8]> {
+		self.data.get().map(Vec::as_slice)
+	}
+
+	pub fn is_loaded(&self) -> bool {
+		self.data.get().is_some()
+	}
+
+	pub fn is_loadable(&self) -> bool {
+		self.kind == ProgramType::Load
+	}
+
+	pub fn file_range(&self) -> Range<u64> {
+		self.offset..self.offset + self.file_size
+	}
+
+	// the zero-filled tail that has no bytes in the file
+	pub fn bss_size(&self) -> u64 {
+		self.memory_size.saturating_sub(self.file_size)
+	}
+
+	pub fn memory_range(&self) -> Range<u64> {
+		self.virtual_address..self.virtual_address + self.memory_size
+	}
+}
+
+impl fmt::Debug for Segment {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_struct("Segment")
+			.field("kind", &self.kind)
+			.field("flags", &self.flags)
+			.field("offset", &self.offset)
+			.field("virtual_address", &self.virtual_address)
+			.field("physical_address", &self.physical_address)
+			.field("file_size", &self.file_size)
+			.field("memory_size", &self.memory_size)
+			.field("alignment", &self.alignment)
+			.field("data", &BodyDebug(&self.data))
+			.finish()
+	}
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ProgramType {
 	Null,
 	Load,
@@ -163,11 +312,34 @@ pub enum ProgramType {
 	SharedLibrary,
 	ProgramHeaders,
 	ThreadLocalStorage,
+	GnuEhFrame,
+	GnuStack,
+	GnuRelro,
+	GnuProperty,
 	OperatingSystem(u32),
 	Processor(u32),
 	Other(u32),
 }
 
+impl ProgramType {
+	pub fn all_known() -> &'static [Self] {
+		&[
+			Self::Null,
+			Self::Load,
+			Self::Dynamic,
+			Self::Interpreter,
+			Self::Note,
+			Self::SharedLibrary,
+			Self::ProgramHeaders,
+			Self::ThreadLocalStorage,
+			Self::GnuEhFrame,
+			Self::GnuStack,
+			Self::GnuRelro,
+			Self::GnuProperty,
+		]
+	}
+}
+
 impl From<u32> for ProgramType {
 	fn from(value: u32) -> Self {
 		match value {
@@ -180,6 +352,10 @@ impl From<u32> for ProgramType {
 			0x6 => Self::ProgramHeaders,
 			0x7 => Self::ThreadLocalStorage,
 			0x8..=0x5FFF_FFFF => Self::Other(value),
+			0x6474_E550 => Self::GnuEhFrame,
+			0x6474_E551 => Self::GnuStack,
+			0x6474_E552 => Self::GnuRelro,
+			0x6474_E553 => Self::GnuProperty,
 			0x6000_0000..=0x6FFF_FFFF => Self::OperatingSystem(value),
 			0x7000_0000..=0x7FFF_FFFF => Self::Processor(value),
 			0x8000_0000..=0xFFFF_FFFF => Self::Other(value),
@@ -198,6 +374,10 @@ impl From<ProgramType> for u32 {
 			ProgramType::SharedLibrary => 0x5,
 			ProgramType::ProgramHeaders => 0x6,
 			ProgramType::ThreadLocalStorage => 0x7,
+			ProgramType::GnuEhFrame => 0x6474_E550,
+			ProgramType::GnuStack => 0x6474_E551,
+			ProgramType::GnuRelro => 0x6474_E552,
+			ProgramType::GnuProperty => 0x6474_E553,
 			ProgramType::Other(value) => value,
 			ProgramType::OperatingSystem(value) => value,
 			ProgramType::Processor(value) => value,
@@ -216,10 +396,80 @@ pub struct Section {
 	pub info: u32,
 	pub address_alignment: u64,
 	pub entry_size: u64,
-	pub data: Vec<u8>,
+	pub(crate) data: OnceCell<Vec<u8>>,
+}
+
+impl Section {
+	// `None` until the body has been read, `Elf::section_data` reads it if needed
+	pub fn data(&self) -> Option<&[u8]> {
+		self.data.get().map(Vec::as_slice)
+	}
+
+	// the whole body as one string, e.g. `.interp`. nulls other than a trailing one are kept,
+	// string tables need splitting instead
+	pub fn as_str(&self) -> Result<&str> {
+		let data = self.loaded_data()?;
+		let data = data.strip_suffix(&[0]).unwrap_or(data);
+		Ok(std::str::from_utf8(data)?)
+	}
+
+	#[cfg(feature = "hash")]
+	pub fn digest(&self) -> Result<[u8; 32]> {
+		Ok(crate::sha256::Sha256::digest(self.loaded_data()?))
+	}
+
+	pub fn is_loaded(&self) -> bool {
+		self.data.get().is_some()
+	}
+
+	fn loaded_data(&self) -> Result<&[u8]> {
+		self.data().ok_or_else(|| Error::new(ErrorKind::NotLoaded, "Section body is not loaded"))
+	}
+
+	pub fn file_range(&self) -> Range<u64> {
+		self.offset..self.offset + self.body_size()
+	}
+
+	// section 0 is `SHT_NULL`, its size may be the real section count
+	fn body_size(&self) -> u64 {
+		match self.kind {
+			SectionType::NoBits | SectionType::Null => 0,
+			_ => self.size,
+		}
+	}
+}
+
+impl fmt::Debug for Section {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_struct("Section")
+			.field("name_index", &self.name_index)
+			.field("kind", &self.kind)
+			.field("flags", &self.flags)
+			.field("address", &self.address)
+			.field("offset", &self.offset)
+			.field("size", &self.size)
+			.field("link", &self.link)
+			.field("info", &self.info)
+			.field("address_alignment", &self.address_alignment)
+			.field("entry_size", &self.entry_size)
+			.field("data", &BodyDebug(&self.data))
+			.finish()
+	}
 }
 
-#[derive(Debug, PartialEq)]
+// bodies can be megabytes, so only their size is shown
+struct BodyDebug<'a>(&'a OnceCell<Vec<u8>>);
+
+impl fmt::Debug for BodyDebug<'_> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0.get() {
+			Some(data) => write!(fmt, "[.. {} bytes ..]", data.len()),
+			None => write!(fmt, "[.. not loaded ..]"),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum SectionType {
 	Null,
 	ProgramData,
@@ -238,11 +488,69 @@ pub enum SectionType {
 	PreInitArray,
 	Group,
 	SymbolTableNameIndices,
+	Relr,
+	LlvmOdrTable,
+	LlvmLinkerOptions,
+	LlvmAddrsig,
+	LlvmDependentLibraries,
+	LlvmSymbolPartition,
+	LlvmCallGraphProfile,
+	LlvmBbAddrMap,
+	LlvmOffloading,
+	LlvmLto,
+	GnuHash,
+	GnuVersionDefinitions,
+	// processor specific types mean different things per machine, see `for_machine`
+	MipsAbiFlags,
 	OperatingSystem(u32),
 	Processor(u32),
 	Other(u32),
 }
 
+impl SectionType {
+	pub fn all_known() -> &'static [Self] {
+		&[
+			Self::Null,
+			Self::ProgramData,
+			Self::SymbolTable,
+			Self::StringTable,
+			Self::RelocationEntriesWithAddends,
+			Self::HashTable,
+			Self::Dynamic,
+			Self::Note,
+			Self::NoBits,
+			Self::RelocationEntries,
+			Self::SharedLibrary,
+			Self::LinkerSymbolTable,
+			Self::InitArray,
+			Self::FinishArray,
+			Self::PreInitArray,
+			Self::Group,
+			Self::SymbolTableNameIndices,
+			Self::Relr,
+			Self::LlvmOdrTable,
+			Self::LlvmLinkerOptions,
+			Self::LlvmAddrsig,
+			Self::LlvmDependentLibraries,
+			Self::LlvmSymbolPartition,
+			Self::LlvmCallGraphProfile,
+			Self::LlvmBbAddrMap,
+			Self::LlvmOffloading,
+			Self::LlvmLto,
+			Self::GnuHash,
+			Self::GnuVersionDefinitions,
+		]
+	}
+
+	// `From<u32>` leaves processor specific types as `Processor`, since it can't know the machine
+	pub fn for_machine(self, machine: Machine) -> Self {
+		match (self, machine) {
+			(Self::Processor(SHT_MIPS_ABIFLAGS), Machine::Mips) => Self::MipsAbiFlags,
+			_ => self,
+		}
+	}
+}
+
 impl From<u32> for SectionType {
 	fn from(value: u32) -> Self {
 		match value {
@@ -264,7 +572,19 @@ impl From<u32> for SectionType {
 			0x10 => Self::PreInitArray,
 			0x11 => Self::Group,
 			0x12 => Self::SymbolTableNameIndices,
-			0x13..=0x5FFF_FFFF => Self::Other(value),
+			0x13 => Self::Relr,
+			0x14..=0x5FFF_FFFF => Self::Other(value),
+			0x6FFF_4C00 => Self::LlvmOdrTable,
+			0x6FFF_4C01 => Self::LlvmLinkerOptions,
+			0x6FFF_4C03 => Self::LlvmAddrsig,
+			0x6FFF_4C04 => Self::LlvmDependentLibraries,
+			0x6FFF_4C05 => Self::LlvmSymbolPartition,
+			0x6FFF_4C09 => Self::LlvmCallGraphProfile,
+			0x6FFF_4C0A => Self::LlvmBbAddrMap,
+			0x6FFF_4C0B => Self::LlvmOffloading,
+			0x6FFF_4C0C => Self::LlvmLto,
+			0x6FFF_FFF6 => Self::GnuHash,
+			0x6FFF_FFFD => Self::GnuVersionDefinitions,
 			0x6000_0000..=0x6FFF_FFFF => Self::OperatingSystem(value),
 			0x7000_0000..=0x7FFF_FFFF => Self::Processor(value),
 			0x8000_0000..=0xFFFF_FFFF => Self::Other(value),
@@ -292,6 +612,19 @@ impl From<SectionType> for u32 {
 			SectionType::PreInitArray => 0x10,
 			SectionType::Group => 0x11,
 			SectionType::SymbolTableNameIndices => 0x12,
+			SectionType::Relr => 0x13,
+			SectionType::LlvmOdrTable => 0x6FFF_4C00,
+			SectionType::LlvmLinkerOptions => 0x6FFF_4C01,
+			SectionType::LlvmAddrsig => 0x6FFF_4C03,
+			SectionType::LlvmDependentLibraries => 0x6FFF_4C04,
+			SectionType::LlvmSymbolPartition => 0x6FFF_4C05,
+			SectionType::LlvmCallGraphProfile => 0x6FFF_4C09,
+			SectionType::LlvmBbAddrMap => 0x6FFF_4C0A,
+			SectionType::LlvmOffloading => 0x6FFF_4C0B,
+			SectionType::LlvmLto => 0x6FFF_4C0C,
+			SectionType::GnuHash => 0x6FFF_FFF6,
+			SectionType::GnuVersionDefinitions => 0x6FFF_FFFD,
+			SectionType::MipsAbiFlags => SHT_MIPS_ABIFLAGS,
 			SectionType::OperatingSystem(value) => value,
 			SectionType::Processor(value) => value,
 			SectionType::Other(value) => value,
@@ -301,28 +634,905 @@ impl From<SectionType> for u32 {
 
 impl Elf {
 	pub fn parse(file: File) -> Result<Elf> {
-		let mut reader = ElfFile::new(file);
+		Parser::new().parse(file)
+	}
+
+	#[deprecated(note = "parsing is lazy by default, use `parse`")]
+	pub fn parse_lazy(file: File) -> Result<Elf> {
+		#[allow(deprecated)]
+		Parser::new().parse_lazy(file)
+	}
+
+	pub fn parse_eager(file: File) -> Result<Elf> {
+		Parser::new().parse_eager(file)
+	}
+
+	pub fn parse_reader<R: Read + Seek + 'static>(reader: R) -> Result<Elf> {
+		Parser::new().parse_reader(reader)
+	}
+
+	pub fn parse_lenient(file: File) -> Result<Elf> {
+		Parser::new().parse_lenient(file)
+	}
+
+	pub fn parse_with_options(file: File, options: ParseOptions) -> Result<Elf> {
+		Parser::with_options(options).parse(file)
+	}
+
+	pub fn parse_with_callback<F: FnMut(ParseProgress) -> ControlFlow<()>>(
+		file: File,
+		callback: F,
+	) -> Result<Elf> {
+		Parser::new().parse_with_callback(file, callback)
+	}
+
+	pub fn parse_sections<R: Read + Seek + 'static>(reader: R, names: &[&str]) -> Result<Elf> {
+		Parser::new().parse_sections(reader, names)
+	}
+
+	// additional images embedded after the first, each can be parsed with
+	// `parse_reader(Cursor::new(&data[offset..]))`
+	pub fn find_elf_offsets(data: &[u8]) -> Vec<u64> {
+		(EMBEDDED_ELF_ALIGNMENT..data.len())
+			.step_by(EMBEDDED_ELF_ALIGNMENT)
+			.filter(|&offset| data.get(offset..offset + ELFMAG.len()) == Some(&ELFMAG))
+			.map(|offset| offset as u64)
+			.collect()
+	}
+
+	// ARM uses the low bit of code addresses to select the Thumb instruction set
+	pub fn entry_is_thumb(&self) -> bool {
+		self.header.machine == Machine::Arm && self.header.entry & 1 != 0
+	}
+
+	pub fn canonical_entry(&self) -> u64 {
+		match self.header.machine {
+			Machine::Arm => self.header.entry & !1,
+			_ => self.header.entry,
+		}
+	}
+
+	pub fn entry_bytes(&self, size: usize) -> Result<Vec<u8>> {
+		let entry = self.canonical_entry();
+		if self.virtual_to_offset(entry).is_none() {
+			return Err(format!("Entry point {entry:#x} is not in any loadable segment").into());
+		}
+
+		self.virtual_data(entry, size as u64)?.map(<[u8]>::to_vec).ok_or_else(|| {
+			format!("{size} bytes at entry point {entry:#x} run past the end of its segment").into()
+		})
+	}
+
+	pub fn section_name(&self, section: &Section) -> Result<&str> {
+		let names = self.section_name_table().ok_or_else(|| {
+			Error::new(ErrorKind::Unsupported, "No valid section name string table")
+		})?;
+
+		read_string(self.section_data(names)?, section.name_index)
+	}
+
+	// `None` for files without one, or whose header points at something else
+	pub(super) fn section_name_table(&self) -> Option<&Section> {
+		match self.header.section_header_names_index {
+			SHN_UNDEF => None,
+			_ => self
+				.sections
+				.get(self.section_names_index())
+				.filter(|it| it.kind == SectionType::StringTable),
+		}
+	}
+
+	pub fn section_names_index(&self) -> usize {
+		section_names_index(&self.header, &self.sections)
+	}
+
+	pub fn section_index_of(&self, section: &Section) -> Option<usize> {
+		self.sections.iter().position(|it| std::ptr::eq(it, section))
+	}
+
+	pub fn section_by_name(&self, name: &str) -> Result<Option<&Section>> {
+		for section in self.sections.iter() {
+			if self.section_name(section)? == name {
+				return Ok(Some(section));
+			}
+		}
+
+		Ok(None)
+	}
+
+	// for addresses from e.g. the dynamic entries, which point at the start of a section. empty
+	// sections can share their address with the next one, so those only match if nothing else does
+	pub fn section_starting_at(&self, address: u64) -> Option<&Section> {
+		let mut starting =
+			self.sections.iter().filter(|it| it.is_allocated() && it.address == address);
+		let first = starting.next()?;
+		if first.size > 0 {
+			return Some(first);
+		}
+		starting.find(|it| it.size > 0).or(Some(first))
+	}
+
+	#[cfg(feature = "hash")]
+	pub fn section_digests(&self) -> Result<Vec<(String, [u8; 32])>> {
+		self.sections
+			.iter()
+			.filter(|it| it.kind != SectionType::Null && it.kind != SectionType::NoBits)
+			.map(|it| {
+				let digest = crate::sha256::Sha256::digest(self.section_data(it)?);
+				Ok((self.section_name(it)?.to_owned(), digest))
+			})
+			.collect()
+	}
+
+	// SHA-256 of the file data of every `PT_LOAD` segment (`p_filesz` bytes, so no bss), in order
+	// of virtual address and header order among equal ones, concatenated with nothing in between.
+	// the file header is left out where a segment maps it, since it points at the section
+	// headers, and anything outside the loadable segments doesn't count either
+	#[cfg(feature = "hash")]
+	pub fn code_identity_hash(&self) -> Result<[u8; 32]> {
+		let mut segments: Vec<_> = self.loadable_segments().collect();
+		segments.sort_by_key(|it| it.virtual_address);
+
+		let mut hasher = crate::sha256::Sha256::new();
+		for segment in segments {
+			let data = self.segment_data(segment)?;
+			let header_end = (self.header.header_size as u64).saturating_sub(segment.offset);
+			let start = header_end.min(data.len() as u64) as usize;
+			hasher.update(&data[start..]);
+		}
+
+		Ok(hasher.finish())
+	}
+
+	pub fn segments(&self) -> &[Segment] {
+		&self.segments
+	}
+
+	pub fn sections(&self) -> &[Section] {
+		&self.sections
+	}
+
+	pub fn segments_of_type(&self, kind: ProgramType) -> impl Iterator<Item = &Segment> {
+		self.segments.iter().filter(move |it| it.kind == kind)
+	}
+
+	pub fn segment_data_of_type(&self, kind: ProgramType) -> Result<Option<&[u8]>> {
+		self.segments_of_type(kind).next().map(|it| self.segment_data(it)).transpose()
+	}
+
+	pub fn loadable_segments(&self) -> impl Iterator<Item = &Segment> {
+		self.segments.iter().filter(|it| it.is_loadable())
+	}
+
+	pub fn first_executable_segment(&self) -> Option<&Segment> {
+		self.loadable_segments().find(|it| it.flags & PF_X != 0)
+	}
+
+	// loaded data no section accounts for, e.g. a packer's unpacking stub. segments with only bss
+	// have no file data, so those are matched by address instead
+	pub fn segments_without_sections(&self) -> Vec<usize> {
+		let overlaps = |a: &Range<u64>, b: &Range<u64>| a.start < b.end && b.start < a.end;
+
+		let has_section = |segment: &Segment| {
+			let file_range = segment.file_range();
+			let memory_range = segment.memory_range();
+
+			self.sections.iter().any(|it| {
+				let address_range = it.address..it.address + it.size;
+				overlaps(&file_range, &it.file_range())
+					|| segment.file_size == 0
+						&& it.is_allocated()
+						&& overlaps(&memory_range, &address_range)
+			})
+		};
+
+		self.segments
+			.iter()
+			.enumerate()
+			.filter(|(_, it)| it.is_loadable() && !has_section(it))
+			.map(|(index, _)| index)
+			.collect()
+	}
+
+	// packed or corrupted files often point their entry somewhere that can't be executed
+	pub fn entry_is_valid(&self) -> bool {
+		let entry = self.canonical_entry();
+		self.loadable_segments()
+			.filter(|it| it.flags & PF_X != 0)
+			.any(|it| it.memory_range().contains(&entry))
+	}
+
+	// the memory all loadable segments take up together, including their bss
+	pub fn loaded_size(&self) -> u64 {
+		self.loadable_segments().map(|it| it.memory_size).sum()
+	}
+
+	// per thread, `.tdata` is copied from the file and `.tbss` zeroed. the segment's memory size
+	// already covers both including the padding between them, so only objects without segments
+	// fall back to adding up the sections
+	pub fn total_tls_size(&self) -> u64 {
+		if let Some(segment) = self.segments_of_type(ProgramType::ThreadLocalStorage).next() {
+			return segment.memory_size;
+		}
+
+		self.sections_with_flags(SectionFlags::TLS).map(|it| it.size).sum()
+	}
+
+	// the span a loader has to reserve, from the lowest segment start to the highest segment end
+	// unlike the size on disk this includes the zero-filled bss
+	pub fn vaddr_range(&self) -> Option<(u64, u64)> {
+		let start = self.loadable_segments().map(|it| it.virtual_address).min()?;
+		let end = self.loadable_segments().map(|it| it.virtual_address + it.memory_size).max()?;
+
+		Some((start, end))
+	}
+
+	pub fn virtual_to_offset(&self, address: u64) -> Option<u64> {
+		self.loadable_segments()
+			.find(|it| address >= it.virtual_address && address - it.virtual_address < it.file_size)
+			.map(|it| address - it.virtual_address + it.offset)
+	}
+
+	// `None` if the range isn't in the file data of one segment, which is loaded if needed
+	pub fn virtual_data(&self, address: u64, size: u64) -> Result<Option<&[u8]>> {
+		let segment = self.loadable_segments().find(|it| {
+			address >= it.virtual_address
+				&& (address - it.virtual_address).checked_add(size) <= Some(it.file_size)
+		});
+		let Some(segment) = segment else {
+			return Ok(None);
+		};
+
+		let start = (address - segment.virtual_address) as usize;
+		Ok(self.segment_data(segment)?.get(start..start + size as usize))
+	}
+
+	pub fn read_virtual(&self, address: u64, size: usize) -> Result<Vec<u8>> {
+		let end = address
+			.checked_add(size as u64)
+			.ok_or_else(|| format!("Range at {address:#x} overflows the address space"))?;
+
+		// the whole range has to be mapped before anything is allocated for it, the size may come
+		// straight from the file
+		let mut pieces = Vec::new();
+		let mut current = address;
+		while current < end {
+			let segment = self
+				.loadable_segments()
+				.find(|it| {
+					current >= it.virtual_address && current - it.virtual_address < it.memory_size
+				})
+				.ok_or_else(|| {
+					format!("Address {current:#x} is not mapped by any loadable segment")
+				})?;
+
+			let start = current - segment.virtual_address;
+			let count = (segment.memory_size - start).min(end - current);
+			pieces.push((segment, start, count));
+			current += count;
+		}
+
+		let mut data = Vec::with_capacity(size);
+		for (segment, start, count) in pieces {
+			// anything past the file-backed part of the segment is zero-filled bss. unloaded
+			// segments are read from directly, rather than loading all of them for a few bytes
+			let file_end = (start + count).min(segment.file_size);
+			if start < file_end {
+				match segment.data() {
+					Some(body) => {
+						let body =
+							body.get(start as usize..file_end as usize).ok_or_else(|| {
+								format!("Segment at {:#x} is truncated", segment.virtual_address)
+							})?;
+						data.extend_from_slice(body);
+					}
+					None => {
+						data.append(&mut self.read_body(segment.offset + start, file_end - start)?)
+					}
+				}
+			}
+			data.resize(data.len() + (start + count - file_end.max(start)) as usize, 0);
+		}
+
+		Ok(data)
+	}
+
+	// unlike `read_virtual` this stops at section boundaries, so a read can't run from e.g.
+	// `.rodata` into whatever the linker placed after it
+	pub fn data_at_vaddr(&self, address: u64, size: usize) -> Result<Cow<'_, [u8]>> {
+		let (index, section) = self
+			.sections
+			.iter()
+			.enumerate()
+			.find(|(_, it)| {
+				it.is_allocated() && address >= it.address && address - it.address < it.size
+			})
+			.ok_or_else(|| format!("Address {address:#x} is not in any allocated section"))?;
+
+		let start = address - section.address;
+		if start + size as u64 > section.size {
+			return Err(format!(
+				"Range of {size:#x} bytes at {address:#x} runs past the end of section {index}"
+			)
+			.into());
+		}
+
+		if section.kind == SectionType::NoBits {
+			return Ok(Cow::Owned(vec![0; size]));
+		}
+
+		let start = start as usize;
+		let data = self.section_data(section)?;
+		data.get(start..start + size)
+			.map(Cow::Borrowed)
+			.ok_or_else(|| format!("Section {index} body is shorter than its size").into())
+	}
+
+	// the buffer starts out zeroed, so only the file-backed part of each segment is copied and
+	// the bss stays zero
+	pub fn memory_image(&self) -> Result<(u64, Vec<u8>)> {
+		let (base, end) = self.vaddr_range().ok_or("File has no loadable segments")?;
+		let size = usize::try_from(end - base)
+			.map_err(|_| format!("Memory image of {:#x} bytes is too large", end - base))?;
+
+		let mut image = vec![0; size];
+		for segment in self.loadable_segments() {
+			let data = self.segment_data(segment)?;
+			let start = (segment.virtual_address - base) as usize;
+			image[start..start + data.len()].copy_from_slice(data);
+		}
+
+		Ok((base, image))
+	}
+
+	// trailing bytes that no header, segment or section refers to, e.g. appended by installers
+	pub fn overlay(&self) -> Option<Range<u64>> {
+		let header = &self.header;
+		let program_headers = header.program_header_offset
+			+ self.segments.len() as u64 * header.program_header_entry_size as u64;
+		let section_headers = header.section_header_offset
+			+ self.sections.len() as u64 * header.section_header_entry_size as u64;
+
+		let segments = self.segments.iter().map(|it| it.file_range().end);
+		let sections = self.sections.iter().map(|it| it.file_range().end);
+
+		let end = segments
+			.chain(sections)
+			.chain([header.header_size as u64, program_headers, section_headers])
+			.max()
+			.unwrap_or_default();
+
+		(end < self.file_size).then_some(end..self.file_size)
+	}
+
+	pub fn reader_for<'a>(&self, data: &'a [u8]) -> Reader<'a> {
+		Reader::new(data, self.header.ident.is_little_endian)
+	}
+
+	// bodies are read on first use and then kept. a section inside a loaded segment is borrowed
+	// from it instead, so the same bytes aren't held twice
+	pub fn section_data<'a>(&'a self, section: &'a Section) -> Result<&'a [u8]> {
+		if let Some(data) = section.data.get() {
+			return Ok(data);
+		}
+
+		if let Some(data) = self.data_in_segments(section) {
+			return Ok(data);
+		}
+
+		let data = self.read_body(section.offset, section.body_size())?;
+		Ok(section.data.get_or_init(|| data))
+	}
+
+	fn data_in_segments(&self, section: &Section) -> Option<&[u8]> {
+		let range = section.file_range();
+		self.segments.iter().find_map(|segment| {
+			let start = range.start.checked_sub(segment.offset)? as usize;
+			segment.data.get()?.get(start..start + (range.end - range.start) as usize)
+		})
+	}
+
+	pub fn segment_data<'a>(&'a self, segment: &'a Segment) -> Result<&'a [u8]> {
+		if let Some(data) = segment.data.get() {
+			return Ok(data);
+		}
+
+		let data = self.read_body(segment.offset, segment.file_size)?;
+		Ok(segment.data.get_or_init(|| data))
+	}
+
+	fn read_body(&self, offset: u64, size: u64) -> Result<Vec<u8>> {
+		self.reader.borrow_mut().read_body(offset, size)
+	}
+
+	// unlike `section_data` this gives the section a copy of its own, even inside a loaded segment
+	pub fn load_section(&mut self, index: usize) -> Result<&[u8]> {
+		let section = self
+			.sections
+			.get(index)
+			.ok_or_else(|| format!("Section index {index} is out of range"))?;
+
+		if !section.is_loaded() {
+			let data = match self.data_in_segments(section) {
+				Some(data) => data.to_vec(),
+				None => self.read_body(section.offset, section.body_size())?,
+			};
+			self.sections[index].data = OnceCell::from(data);
+		}
+
+		Ok(self.sections[index].data.get_or_init(Vec::new))
+	}
+
+	pub fn load_segment(&mut self, index: usize) -> Result<&[u8]> {
+		let segment = self
+			.segments
+			.get_mut(index)
+			.ok_or_else(|| format!("Segment index {index} is out of range"))?;
+
+		if !segment.is_loaded() {
+			let data = self.reader.get_mut().read_body(segment.offset, segment.file_size)?;
+			segment.data = OnceCell::from(data);
+		}
+
+		Ok(segment.data.get_or_init(Vec::new))
+	}
+
+	pub fn load_all(&mut self) -> Result<()> {
+		for index in 0..self.segments.len() {
+			self.load_segment(index)?;
+		}
+		self.load_sections()
+	}
+
+	// edits that move sections need them in memory, their old offsets stop being valid
+	pub(super) fn load_sections(&mut self) -> Result<()> {
+		for index in 0..self.sections.len() {
+			self.load_section(index)?;
+		}
+
+		Ok(())
+	}
+
+	pub fn reread_section(&mut self, index: usize) -> Result<()> {
+		let section = self
+			.sections
+			.get_mut(index)
+			.ok_or_else(|| format!("Section index {index} is out of range"))?;
+
+		let data = self.reader.get_mut().read_body(section.offset, section.body_size())?;
+		section.data = OnceCell::from(data);
+		self.symbols.take();
+
+		Ok(())
+	}
+
+	pub fn reread_segment(&mut self, index: usize) -> Result<()> {
+		let segment = self
+			.segments
+			.get_mut(index)
+			.ok_or_else(|| format!("Segment index {index} is out of range"))?;
+
+		let data = self.reader.get_mut().read_body(segment.offset, segment.file_size)?;
+		segment.data = OnceCell::from(data);
+		self.symbols.take();
+
+		Ok(())
+	}
+
+	// same CRC-32 as zlib (reflected 0xEDB88320, inverted on entry and exit), which is what
+	// binutils and gdb use for `.gnu_debuglink`
+	pub fn crc32(&self) -> Result<u32> {
+		let mut reader = self.reader.borrow_mut();
+		reader.seek(SeekFrom::Start(0))?;
+
+		let mut crc = !0u32;
+		let mut buffer = [0; 8192];
+
+		loop {
+			let count = reader.inner.read(&mut buffer)?;
+			if count == 0 {
+				break;
+			}
+
+			for byte in &buffer[..count] {
+				crc = CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+			}
+		}
+
+		Ok(!crc)
+	}
+}
+
+// the parsed file keeps its reader around for lazy loads, so the bytes are copied
+impl TryFrom<&[u8]> for Elf {
+	type Error = Error;
+
+	fn try_from(bytes: &[u8]) -> Result<Elf> {
+		Elf::parse_reader(io::Cursor::new(bytes.to_vec()))
+	}
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ElfClass {
+	Elf32,
+	Elf64,
+}
+
+// cheap checks for sniffing file types without a full parse
+pub fn is_elf(bytes: &[u8]) -> bool {
+	bytes.starts_with(&ELFMAG)
+}
+
+pub fn elf_class(bytes: &[u8]) -> Option<ElfClass> {
+	if !is_elf(bytes) {
+		return None;
+	}
+
+	match *bytes.get(4)? {
+		ELFCLASS32 => Some(ElfClass::Elf32),
+		ELFCLASS64 => Some(ElfClass::Elf64),
+		_ => None,
+	}
+}
+
+fn read_string(data: &[u8], offset: usize) -> Result<&str> {
+	let bytes =
+		data.get(offset..).ok_or_else(|| format!("String offset {offset} is out of range"))?;
+	let end = bytes
+		.iter()
+		.position(|it| *it == 0)
+		.ok_or_else(|| format!("String at offset {offset} is not null-terminated"))?;
+
+	Ok(std::str::from_utf8(&bytes[..end])?)
+}
+
+// with `SHN_XINDEX` in `e_shstrndx` the real index is the `sh_link` of section 0
+fn section_names_index(header: &FileHeader, sections: &[Section]) -> usize {
+	match header.section_header_names_index {
+		SHN_XINDEX => sections.first().map_or(0, |it| it.link as usize),
+		index => index.into(),
+	}
+}
+
+const CRC32_TABLE: [u32; 256] = {
+	let mut table = [0; 256];
+
+	let mut index = 0;
+	while index < 256 {
+		let mut crc = index as u32;
+
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 1 != 0 {
+				(crc >> 1) ^ 0xEDB8_8320
+			} else {
+				crc >> 1
+			};
+			bit += 1;
+		}
+
+		table[index] = crc;
+		index += 1;
+	}
+
+	table
+};
+
+#[derive(Clone, Copy)]
+pub struct ParseOptions {
+	pub buffer_capacity: usize,
+	// rejects files with anything in the `e_ident` padding
+	pub strict_ident_padding: bool,
+	// bodies are read on demand unless asked for here, in one pass while parsing. segments and
+	// sections mostly cover the same bytes, so loading both holds most of the file twice
+	pub load_segments: bool,
+	pub load_sections: bool,
+}
+
+impl Default for ParseOptions {
+	fn default() -> Self {
+		ParseOptions {
+			buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+			strict_ident_padding: false,
+			load_segments: false,
+			load_sections: false,
+		}
+	}
+}
+
+// reuses the header table buffer between files, for tools that parse many files in a row
+#[derive(Default)]
+pub struct Parser {
+	table: Vec<u8>,
+	options: ParseOptions,
+}
+
+impl Parser {
+	pub fn new() -> Self {
+		Parser::default()
+	}
+
+	pub fn with_options(options: ParseOptions) -> Self {
+		Parser {
+			table: Vec::new(),
+			options,
+		}
+	}
+
+	// only reads the headers and the bodies `ParseOptions` asks for, the rest are read when first
+	// used through `Elf::section_data` and friends
+	pub fn parse(&mut self, file: File) -> Result<Elf> {
+		self.parse_source(Box::new(file), Bodies::Options, None)
+	}
+
+	#[deprecated(note = "parsing is lazy by default, use `parse`")]
+	pub fn parse_lazy(&mut self, file: File) -> Result<Elf> {
+		self.parse_source(Box::new(file), Bodies::Skip, None)
+	}
+
+	// reads every body up front, for tools that will look at all of them anyway
+	pub fn parse_eager(&mut self, file: File) -> Result<Elf> {
+		self.parse_source(Box::new(file), Bodies::All, None)
+	}
+
+	// bodies that can't be read are left empty and reported in `Elf::warnings` instead of failing
+	// the whole parse, the headers still have to be intact
+	pub fn parse_lenient(&mut self, file: File) -> Result<Elf> {
+		self.parse_source(Box::new(file), Bodies::Lenient, None)
+	}
+
+	// the callback is told about every body read, and can cancel the parse by returning `Break`
+	pub fn parse_with_callback<F: FnMut(ParseProgress) -> ControlFlow<()>>(
+		&mut self,
+		file: File,
+		mut callback: F,
+	) -> Result<Elf> {
+		self.parse_source(Box::new(file), Bodies::All, Some(&mut callback))
+	}
+
+	pub fn parse_reader<R: Read + Seek + 'static>(&mut self, reader: R) -> Result<Elf> {
+		self.parse_source(Box::new(reader), Bodies::Options, None)
+	}
+
+	// segment bodies are left unloaded as well, they overlap the sections that weren't asked for
+	pub fn parse_sections<R: Read + Seek + 'static>(
+		&mut self,
+		reader: R,
+		names: &[&str],
+	) -> Result<Elf> {
+		let mut elf = self.parse_source(Box::new(reader), Bodies::Skip, None)?;
+
+		// the names have to be resolvable before deciding which bodies to load
+		if elf.section_name_table().is_some() {
+			elf.load_section(elf.section_names_index())?;
+		}
+
+		let mut wanted = Vec::new();
+		for (index, section) in elf.sections.iter().enumerate() {
+			if names.contains(&elf.section_name(section)?) {
+				wanted.push(index);
+			}
+		}
+
+		for index in wanted {
+			elf.load_section(index)?;
+		}
+
+		Ok(elf)
+	}
+
+	fn parse_source(
+		&mut self,
+		source: Box<dyn Source>,
+		bodies: Bodies,
+		progress: Option<&mut dyn FnMut(ParseProgress) -> ControlFlow<()>>,
+	) -> Result<Elf> {
+		let mut reader = ElfFile::new(source, self.options.buffer_capacity);
+		let mut warnings = Vec::new();
+
+		let file_size = reader.seek(SeekFrom::End(0))?;
+		reader.seek(SeekFrom::Start(0))?;
+		reader.length = file_size;
 
 		let header = reader.read_header()?;
-		let segments = reader
-			.read_segments(header.program_header_offset, header.program_header_count.into())?;
-		let sections = reader
-			.read_sections(header.section_header_offset, header.section_header_count.into())?;
+		if self.options.strict_ident_padding && header.ident.padding != [0; 7] {
+			return Err(Error::malformed(format!(
+				"Ident padding {:02x?} is not zeroed",
+				header.ident.padding
+			)));
+		}
+
+		// counts too large for the header are kept in section 0, `sh_size` for sections and
+		// `sh_info` for program headers
+		let is_extended_section_count =
+			header.section_header_count == 0 && header.section_header_offset != 0;
+		let first = if header.program_header_count == PN_XNUM || is_extended_section_count {
+			if header.section_header_offset == 0 {
+				return Err(Error::malformed(
+					"Extended program header count without a section header table",
+				));
+			}
+			reader
+				.read_sections(
+					header.section_header_offset,
+					1,
+					header.section_header_entry_size.into(),
+					&mut self.table,
+				)?
+				.pop()
+		} else {
+			None
+		};
+
+		let segment_count = match &first {
+			Some(first) if header.program_header_count == PN_XNUM => {
+				// the count is only 32 bits, but that's still enough to make a table larger than
+				// memory
+				let count = first.info as u64;
+				let table_end = (header.program_header_entry_size as u64)
+					.checked_mul(count)
+					.and_then(|it| it.checked_add(header.program_header_offset));
+				if table_end.is_none_or(|it| it > file_size) {
+					return Err(Error::malformed(format!(
+						"Extended program header count {count} doesn't fit in the file"
+					)));
+				}
+				count as usize
+			}
+			_ => header.program_header_count.into(),
+		};
+		let section_count = match &first {
+			Some(first) if is_extended_section_count => {
+				let count = first.size;
+				let table_end = (header.section_header_entry_size as u64)
+					.checked_mul(count)
+					.and_then(|it| it.checked_add(header.section_header_offset));
+				if table_end.is_none_or(|it| it > file_size) {
+					return Err(Error::malformed(format!(
+						"Extended section header count {count} doesn't fit in the file"
+					)));
+				}
+				count as usize
+			}
+			_ => header.section_header_count.into(),
+		};
+
+		let mut segments = reader.read_segments(
+			header.program_header_offset,
+			segment_count,
+			header.program_header_entry_size.into(),
+			&mut self.table,
+		)?;
+		let mut sections = reader.read_sections(
+			header.section_header_offset,
+			section_count,
+			header.section_header_entry_size.into(),
+			&mut self.table,
+		)?;
+		for section in &mut sections {
+			section.kind = section.kind.for_machine(header.machine);
+		}
+
+		// names are resolved from this table, so they're unavailable if it points at e.g. code.
+		// everything that doesn't need them still works
+		let names_index = header.section_header_names_index;
+		if names_index != SHN_UNDEF && !sections.is_empty() {
+			let is_valid = sections
+				.get(section_names_index(&header, &sections))
+				.is_some_and(|it| it.kind == SectionType::StringTable);
+			if !is_valid {
+				warnings.push(Warning::InvalidSectionNameTable { index: names_index });
+			}
+		}
+
+		let (load_segments, load_sections) = match bodies {
+			Bodies::Skip => (false, false),
+			Bodies::Options => (self.options.load_segments, self.options.load_sections),
+			Bodies::All | Bodies::Lenient => (true, true),
+		};
+		if load_segments || load_sections {
+			let lenient = bodies == Bodies::Lenient;
+			let segments_to_read: &mut [_] = if load_segments {
+				&mut segments
+			} else {
+				&mut []
+			};
+			let sections_to_read: &mut [_] = if load_sections {
+				&mut sections
+			} else {
+				&mut []
+			};
+			reader.read_bodies(
+				segments_to_read,
+				sections_to_read,
+				lenient,
+				&mut warnings,
+				progress,
+			)?;
+		}
 
 		Ok(Elf {
 			header,
 			segments,
 			sections,
+			warnings,
+			file_size,
+			reader: RefCell::new(reader),
+			symbols: OnceCell::new(),
 		})
 	}
 }
 
-const ELF_CLASS_64: u8 = 0x02;
-const ELF_DATA_LE: u8 = 0x01;
+#[derive(Debug)]
+pub enum Warning {
+	UnreadableSegment {
+		index: usize,
+		error: Error,
+	},
+	UnreadableSection {
+		index: usize,
+		error: Error,
+	},
+	UnsupportedRelocation {
+		offset: u64,
+		kind: u32,
+	},
+	InvalidSectionNameTable {
+		index: u16,
+	},
+	MachineClassMismatch {
+		machine: Machine,
+		is_64_bit: bool,
+	},
+	MachineEndiannessMismatch {
+		machine: Machine,
+		is_little_endian: bool,
+	},
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParseProgress {
+	pub bytes_read: u64,
+	pub total_bytes: u64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Bodies {
+	Skip,
+	// whatever `ParseOptions` asks for
+	Options,
+	All,
+	// all of them, but failing ones are only warned about
+	Lenient,
+}
+
+fn report_progress(
+	progress: &mut Option<&mut dyn FnMut(ParseProgress) -> ControlFlow<()>>,
+	state: ParseProgress,
+) -> Result<()> {
+	if let Some(callback) = progress {
+		if callback(state).is_break() {
+			return Err(Error::new(ErrorKind::Cancelled, "Parsing was cancelled"));
+		}
+	}
+	Ok(())
+}
+
+const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+const FILE_HEADER_SIZE: usize = 64;
+const PROGRAM_HEADER_SIZE: usize = 56;
+const EMBEDDED_ELF_ALIGNMENT: usize = 8;
+const SECTION_HEADER_SIZE: usize = 64;
+
+trait Source: Read + Seek {}
+
+impl<T: Read + Seek> Source for T {}
 
 pub struct ElfFile {
 	is_little_endian: bool,
-	inner: BufReader<File>,
+	// reads are checked against this up front, so they can say how much was missing
+	length: u64,
+	inner: BufReader<Box<dyn Source>>,
 }
 
 impl Seek for ElfFile {
@@ -332,10 +1542,11 @@ impl Seek for ElfFile {
 }
 
 impl ElfFile {
-	fn new(file: File) -> Self {
+	fn new(source: Box<dyn Source>, buffer_capacity: usize) -> Self {
 		ElfFile {
 			is_little_endian: true,
-			inner: BufReader::new(file),
+			length: u64::MAX,
+			inner: BufReader::with_capacity(buffer_capacity, source),
 		}
 	}
 
@@ -359,14 +1570,20 @@ impl ElfFile {
 	}
 
 	fn read_ident(&mut self) -> Result<Ident> {
-		let magic = self.read_u32()?;
-		let is_64_bit = self.read_u8()? == ELF_CLASS_64;
+		// the byte order isn't known yet, so the magic has to be compared byte by byte
+		let bytes: [u8; 4] = self.read_array()?;
+		let magic = u32::from_le_bytes(bytes);
+		let is_64_bit = self.read_u8()? == ELFCLASS64;
 
-		if magic != 0x464C457F || !is_64_bit {
+		if bytes != ELFMAG || !is_64_bit {
 			return Err(Error::from("File format is not ELF64!"));
 		}
 
-		let is_little_endian = self.read_u8()? == ELF_DATA_LE;
+		let is_little_endian = match self.read_u8()? {
+			ELFDATA2LSB => true,
+			ELFDATA2MSB => false,
+			data => return Err(Error::malformed(format!("Unknown data encoding {data}"))),
+		};
 		self.is_little_endian = is_little_endian;
 
 		let version = self.read_u8()?;
@@ -378,8 +1595,7 @@ impl ElfFile {
 
 		let abi_version = self.read_u8()?;
 
-		// ignore padding
-		self.seek(SeekFrom::Current(7))?;
+		let padding = self.read_array()?;
 
 		Ok(Ident {
 			magic,
@@ -388,32 +1604,50 @@ impl ElfFile {
 			version,
 			os_abi,
 			abi_version,
+			padding,
 		})
 	}
 
-	fn read_segments(&mut self, offset: u64, count: usize) -> io::Result<Vec<Segment>> {
-		let mut segments = Vec::with_capacity(count);
-
-		self.seek(SeekFrom::Start(offset))?;
-
-		for _ in 0..count {
-			let kind = self.read_u32().map(ProgramType::from)?;
-			let flags = self.read_u32()?;
-			let offset = self.read_u64()?;
-			let virtual_address = self.read_u64()?;
-			let physical_address = self.read_u64()?;
-			let file_size = self.read_u64()?;
-			let memory_size = self.read_u64()?;
-			let alignment = self.read_u64()?;
-
-			let previous_pos = self.stream_position()?;
+	// entries may be padded beyond the fields read here, so they're read at `entry_size` strides
+	fn read_segments(
+		&mut self,
+		offset: u64,
+		count: usize,
+		entry_size: usize,
+		table: &mut Vec<u8>,
+	) -> Result<Vec<Segment>> {
+		if count > 0 && entry_size < PROGRAM_HEADER_SIZE {
+			return Err(Error::malformed(format!(
+				"Program header entry size {entry_size} is smaller than {PROGRAM_HEADER_SIZE}"
+			)));
+		}
 
-			self.seek(SeekFrom::Start(offset))?;
+		let size = count.checked_mul(entry_size).ok_or_else(|| {
+			Error::malformed(format!(
+				"Program header table of {count} entries doesn't fit in memory"
+			))
+		})?;
+		// allocated once the table is known to be in the file, the count can be made up
+		let mut entries = self.read_table(offset, size, table)?;
+		let mut segments = Vec::with_capacity(count);
 
-			let mut data = vec![0; file_size as usize];
-			self.inner.read_exact(data.as_mut_slice())?;
+		for index in 0..count {
+			entries.seek(index * entry_size)?;
+			let kind = entries.read_u32().map(ProgramType::from)?;
+			let flags = entries.read_u32()?;
+			let offset = entries.read_u64()?;
+			let virtual_address = entries.read_u64()?;
+			let physical_address = entries.read_u64()?;
+			let file_size = entries.read_u64()?;
+			let memory_size = entries.read_u64()?;
+			let alignment = entries.read_u64()?;
 
-			self.seek(SeekFrom::Start(previous_pos))?;
+			// only loaded segments have to fit in memory, e.g. core dump notes have no memory size
+			if kind == ProgramType::Load && file_size > memory_size {
+				return Err(Error::malformed(format!(
+					"Segment {index} has file size {file_size:#x} larger than its memory size {memory_size:#x}"
+				)));
+			}
 
 			segments.push(Segment {
 				kind,
@@ -424,38 +1658,47 @@ impl ElfFile {
 				file_size,
 				memory_size,
 				alignment,
-				data,
+				data: OnceCell::new(),
 			});
 		}
 
 		Ok(segments)
 	}
 
-	fn read_sections(&mut self, offset: u64, count: usize) -> io::Result<Vec<Section>> {
-		let mut sections = Vec::with_capacity(count);
-
-		self.seek(SeekFrom::Start(offset))?;
-
-		for _ in 0..count {
-			let name_index = self.read_u32()? as usize;
-			let kind = self.read_u32().map(SectionType::from)?;
-			let flags = self.read_u64()?;
-			let address = self.read_u64()?;
-			let offset = self.read_u64()?;
-			let size = self.read_u64()?;
-			let link = self.read_u32()?;
-			let info = self.read_u32()?;
-			let address_alignment = self.read_u64()?;
-			let entry_size = self.read_u64()?;
-
-			let previous_pos = self.stream_position()?;
-
-			self.seek(SeekFrom::Start(offset))?;
+	fn read_sections(
+		&mut self,
+		offset: u64,
+		count: usize,
+		entry_size: usize,
+		table: &mut Vec<u8>,
+	) -> Result<Vec<Section>> {
+		if count > 0 && entry_size < SECTION_HEADER_SIZE {
+			return Err(Error::malformed(format!(
+				"Section header entry size {entry_size} is smaller than {SECTION_HEADER_SIZE}"
+			)));
+		}
 
-			let mut data = vec![0; size as usize];
-			self.inner.read_exact(data.as_mut_slice())?;
+		let size = count.checked_mul(entry_size).ok_or_else(|| {
+			Error::malformed(format!(
+				"Section header table of {count} entries doesn't fit in memory"
+			))
+		})?;
+		// allocated once the table is known to be in the file, the count can be made up
+		let mut entries = self.read_table(offset, size, table)?;
+		let mut sections = Vec::with_capacity(count);
 
-			self.seek(SeekFrom::Start(previous_pos))?;
+		for index in 0..count {
+			entries.seek(index * entry_size)?;
+			let name_index = entries.read_u32()? as usize;
+			let kind = entries.read_u32().map(SectionType::from)?;
+			let flags = entries.read_u64()?;
+			let address = entries.read_u64()?;
+			let offset = entries.read_u64()?;
+			let size = entries.read_u64()?;
+			let link = entries.read_u32()?;
+			let info = entries.read_u32()?;
+			let address_alignment = entries.read_u64()?;
+			let entry_size = entries.read_u64()?;
 
 			sections.push(Section {
 				name_index,
@@ -468,22 +1711,118 @@ impl ElfFile {
 				info,
 				address_alignment,
 				entry_size,
-				data,
+				data: OnceCell::new(),
 			});
 		}
 
 		Ok(sections)
 	}
 
-	fn read_u8(&mut self) -> io::Result<u8> {
-		let mut buffer = [0; 1];
-		self.inner.read_exact(&mut buffer)?;
-		Ok(buffer[0])
+	// bodies are read after both header tables, so the total is known for reporting progress
+	fn read_bodies(
+		&mut self,
+		segments: &mut [Segment],
+		sections: &mut [Section],
+		lenient: bool,
+		warnings: &mut Vec<Warning>,
+		mut progress: Option<&mut dyn FnMut(ParseProgress) -> ControlFlow<()>>,
+	) -> Result<()> {
+		let segment_bytes: u64 = segments.iter().map(|it| it.file_size).sum();
+		let section_bytes: u64 = sections.iter().map(|it| it.body_size()).sum();
+		let mut state = ParseProgress {
+			bytes_read: 0,
+			total_bytes: segment_bytes + section_bytes,
+		};
+
+		for (index, segment) in segments.iter_mut().enumerate() {
+			match self.read_body(segment.offset, segment.file_size) {
+				Ok(data) => segment.data = OnceCell::from(data),
+				Err(error) if lenient => warnings.push(Warning::UnreadableSegment { index, error }),
+				Err(error) => return Err(error),
+			}
+
+			state.bytes_read += segment.file_size;
+			report_progress(&mut progress, state)?;
+		}
+
+		for (index, section) in sections.iter_mut().enumerate() {
+			match self.read_body(section.offset, section.body_size()) {
+				Ok(data) => section.data = OnceCell::from(data),
+				Err(error) if lenient => warnings.push(Warning::UnreadableSection { index, error }),
+				Err(error) => return Err(error),
+			}
+
+			state.bytes_read += section.body_size();
+			report_progress(&mut progress, state)?;
+		}
+
+		Ok(())
+	}
+
+	// reads a whole header table in one go, since the fields are decoded from memory anyway
+	fn read_table<'a>(
+		&mut self,
+		offset: u64,
+		size: usize,
+		table: &'a mut Vec<u8>,
+	) -> Result<Reader<'a>> {
+		table.clear();
+
+		if size > 0 {
+			self.seek(SeekFrom::Start(offset))?;
+			self.check_remaining(size as u64)?;
+			table.resize(size, 0);
+			self.inner.read_exact(table)?;
+		}
+
+		Ok(Reader::new(table, self.is_little_endian))
+	}
+
+	fn read_body(&mut self, offset: u64, size: u64) -> Result<Vec<u8>> {
+		// empty bodies (e.g. the null section) may have a meaningless offset, so don't seek to it
+		if size == 0 {
+			return Ok(Vec::new());
+		}
+
+		self.seek(SeekFrom::Start(offset))?;
+		self.check_remaining(size)?;
+
+		let mut data = vec![0; size as usize];
+
+		// seeking empties the buffer, so bodies too big to benefit from it go straight to the source
+		if data.len() >= self.inner.capacity() {
+			self.inner.get_mut().read_exact(data.as_mut_slice())?;
+		} else {
+			self.inner.read_exact(data.as_mut_slice())?;
+		}
+
+		Ok(data)
+	}
+
+	fn check_remaining(&mut self, wanted: u64) -> Result<()> {
+		let offset = self.inner.stream_position()?;
+		let available = self.length.saturating_sub(offset);
+		if wanted > available {
+			return Err(Error::unexpected_eof(offset, wanted, available));
+		}
+
+		Ok(())
 	}
 
-	fn read_u16(&mut self) -> io::Result<u16> {
-		let mut buffer = [0; 2];
+	fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+		self.check_remaining(N as u64)?;
+		let mut buffer = [0; N];
 		self.inner.read_exact(&mut buffer)?;
+		Ok(buffer)
+	}
+
+	fn read_u8(&mut self) -> Result<u8> {
+		let [byte] = self.read_array()?;
+		Ok(byte)
+	}
+
+	fn read_u16(&mut self) -> Result<u16> {
+		let buffer = self.read_array()?;
 		if self.is_little_endian {
 			Ok(u16::from_le_bytes(buffer))
 		} else {
@@ -491,9 +1830,8 @@ impl ElfFile {
 		}
 	}
 
-	fn read_u32(&mut self) -> io::Result<u32> {
-		let mut buffer = [0; 4];
-		self.inner.read_exact(&mut buffer)?;
+	fn read_u32(&mut self) -> Result<u32> {
+		let buffer = self.read_array()?;
 		if self.is_little_endian {
 			Ok(u32::from_le_bytes(buffer))
 		} else {
@@ -501,9 +1839,8 @@ impl ElfFile {
 		}
 	}
 
-	fn read_u64(&mut self) -> io::Result<u64> {
-		let mut buffer = [0; 8];
-		self.inner.read_exact(&mut buffer)?;
+	fn read_u64(&mut self) -> Result<u64> {
+		let buffer = self.read_array()?;
 		if self.is_little_endian {
 			Ok(u64::from_le_bytes(buffer))
 		} else {
@@ -511,3 +1848,517 @@ impl ElfFile {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::rc::Rc;
+
+	use super::*;
+	use crate::testing::{
+		fixture, fixture_bytes, fixture_path, program_header_at, read_u64_at, section_header_at,
+		write_u16_at, write_u32_at, write_u64_at,
+	};
+
+	// counts the bytes the parser pulls from the source
+	struct CountingReader {
+		inner: io::Cursor<Vec<u8>>,
+		count: Rc<std::cell::Cell<u64>>,
+	}
+
+	impl Read for CountingReader {
+		fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+			let count = self.inner.read(buffer)?;
+			self.count.set(self.count.get() + count as u64);
+			Ok(count)
+		}
+	}
+
+	impl Seek for CountingReader {
+		fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+			self.inner.seek(pos)
+		}
+	}
+
+	fn parse_eager_bytes(bytes: Vec<u8>) -> Result<Elf> {
+		let options = ParseOptions {
+			load_segments: true,
+			load_sections: true,
+			..ParseOptions::default()
+		};
+		Parser::with_options(options).parse_reader(io::Cursor::new(bytes))
+	}
+
+	fn parse_counting(options: ParseOptions) -> (Elf, u64) {
+		let count = Rc::default();
+		let reader = CountingReader {
+			inner: io::Cursor::new(fixture_bytes("sample")),
+			count: Rc::clone(&count),
+		};
+		let elf = Parser::with_options(options).parse_reader(reader).unwrap();
+		(elf, count.get())
+	}
+
+	#[test]
+	fn parse_leaves_bodies_unloaded() {
+		let elf = fixture("sample");
+
+		assert!(elf.segments().iter().all(|it| !it.is_loaded()));
+		assert!(elf.sections().iter().all(|it| !it.is_loaded()));
+	}
+
+	#[test]
+	fn lazy_bodies_load_on_demand() {
+		let lazy = fixture("sample");
+		let eager = Elf::parse_eager(File::open(fixture_path("sample")).unwrap()).unwrap();
+
+		assert_eq!(lazy.entry_bytes(16).unwrap(), eager.entry_bytes(16).unwrap());
+		assert_eq!(lazy.symbols().unwrap().len(), eager.symbols().unwrap().len());
+		assert!(lazy.section_by_name(".text").unwrap().is_some());
+		assert_eq!(lazy.needed_libraries().unwrap(), ["libc.so.6"]);
+	}
+
+	#[test]
+	fn sections_borrow_from_loaded_segments() {
+		let elf = fixture("sample");
+		let text = elf.section_by_name(".text").unwrap().unwrap();
+		let segment =
+			elf.loadable_segments().find(|it| it.file_range().contains(&text.offset)).unwrap();
+
+		let segment_data = elf.segment_data(segment).unwrap();
+		let start = (text.offset - segment.offset) as usize;
+		let data = elf.section_data(text).unwrap();
+
+		assert_eq!(data, &segment_data[start..start + text.size as usize]);
+		assert!(!text.is_loaded());
+	}
+
+	#[test]
+	fn read_virtual_fails_across_segment_gap() {
+		let elf = fixture("sample");
+		let first = elf.loadable_segments().next().unwrap();
+		let end = first.virtual_address + first.memory_size;
+
+		assert!(elf.read_virtual(end - 4, 4).is_ok());
+		assert!(elf.read_virtual(end - 4, 8).is_err());
+
+		// checked before anything is allocated for it
+		let error = elf.read_virtual(end - 4, usize::MAX / 2).unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			format!("Address {end:#x} is not mapped by any loadable segment")
+		);
+	}
+
+	#[test]
+	fn read_virtual_zero_fills_bss() {
+		let elf = fixture("sample");
+		let data = elf.section_by_name(".data").unwrap().unwrap();
+		let bss = elf.section_by_name(".bss").unwrap().unwrap();
+		let tail = data.size as usize - 8;
+
+		let bytes = elf.read_virtual(data.address + tail as u64, 8 + bss.size as usize).unwrap();
+
+		assert_eq!(&bytes[..8], &elf.section_data(data).unwrap()[tail..]);
+		assert!(bytes[8..].iter().all(|it| *it == 0));
+	}
+
+	#[test]
+	fn virtual_reads_load_unloaded_segments() {
+		let elf = fixture("sample");
+		let segment = elf.loadable_segments().nth(1).unwrap();
+		let address = segment.virtual_address;
+
+		assert!(!segment.is_loaded());
+		let read = elf.read_virtual(address, 16).unwrap();
+		assert_eq!(elf.virtual_data(address, 16).unwrap().unwrap(), read.as_slice());
+		assert!(segment.is_loaded());
+	}
+
+	#[test]
+	fn skipping_segment_bodies_reads_less() {
+		let sections_only = ParseOptions {
+			load_sections: true,
+			..ParseOptions::default()
+		};
+		let everything = ParseOptions {
+			load_segments: true,
+			..sections_only
+		};
+
+		let (elf, sections_read) = parse_counting(sections_only);
+		let (_, everything_read) = parse_counting(everything);
+
+		assert!(sections_read < everything_read);
+		assert!(elf.sections().iter().all(Section::is_loaded));
+		assert!(elf.segments().iter().all(|it| !it.is_loaded()));
+	}
+
+	#[test]
+	fn skipped_segment_bodies_are_read_when_used() {
+		let (elf, _) = parse_counting(ParseOptions {
+			load_sections: true,
+			..ParseOptions::default()
+		});
+
+		assert_eq!(elf.needed_libraries().unwrap(), ["libc.so.6"]);
+		assert!(!elf.dynamic_entries().unwrap().is_empty());
+		assert_eq!(elf.relr_relocations().unwrap().len(), 4);
+		assert!(elf.interpreter().unwrap().is_some());
+	}
+
+	#[test]
+	fn null_section_body_is_empty_without_reading_its_offset() {
+		let mut bytes = fixture_bytes("sample");
+		let null = section_header_at(&bytes, 0);
+		// `sh_offset`, far past the end of the file
+		write_u64_at(&mut bytes, null + 0x18, 0xFFFF_FFFF_0000);
+
+		let elf = parse_eager_bytes(bytes).unwrap();
+		let null = &elf.sections()[0];
+
+		assert_eq!(null.kind, SectionType::Null);
+		assert_eq!(null.data(), Some(&[][..]));
+	}
+
+	#[test]
+	fn empty_segments_are_loaded_empty() {
+		let elf = parse_eager_bytes(fixture_bytes("sample")).unwrap();
+		let stack = elf.segments_of_type(ProgramType::GnuStack).next().unwrap();
+
+		assert_eq!(stack.file_size, 0);
+		assert_eq!(stack.data(), Some(&[][..]));
+	}
+
+	#[test]
+	fn section_name_table_of_wrong_type_is_rejected() {
+		let elf = fixture("sample");
+		let text = elf.section_by_name(".text").unwrap().unwrap();
+		let text = elf.section_index_of(text).unwrap();
+
+		let mut bytes = fixture_bytes("sample");
+		// `e_shstrndx`
+		write_u16_at(&mut bytes, 0x3E, text as u16);
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		assert!(matches!(
+			elf.warnings.as_slice(),
+			[Warning::InvalidSectionNameTable { index }] if *index as usize == text
+		));
+		let error = elf.section_name(&elf.sections()[1]).unwrap_err();
+		assert_eq!(error.kind(), &ErrorKind::Unsupported);
+	}
+
+	#[test]
+	fn bss_size_is_the_part_without_file_data() {
+		let elf = fixture("sample");
+		let data = elf.loadable_segments().last().unwrap();
+
+		assert_eq!(data.bss_size(), data.memory_size - data.file_size);
+		assert!(data.bss_size() > 0);
+		assert_eq!(elf.loadable_segments().next().unwrap().bss_size(), 0);
+	}
+
+	#[test]
+	fn loadable_segment_with_more_file_than_memory_is_malformed() {
+		let elf = fixture("sample");
+		let index = elf.segments().iter().rposition(Segment::is_loadable).unwrap();
+		let memory_size = elf.segments()[index].memory_size;
+
+		let mut bytes = fixture_bytes("sample");
+		let header = program_header_at(&bytes, index);
+		// `p_filesz`
+		write_u64_at(&mut bytes, header + 0x20, memory_size + 1);
+
+		let error = Elf::try_from(bytes.as_slice()).err().unwrap();
+		assert_eq!(error.kind(), &ErrorKind::Malformed);
+	}
+
+	#[test]
+	fn header_enums_round_trip() {
+		for &it in OsAbi::all_known() {
+			assert_eq!(OsAbi::from(u8::from(it)), it);
+		}
+		for value in 0..=u8::MAX {
+			assert_eq!(u8::from(OsAbi::from(value)), value);
+		}
+
+		for &it in FileType::all_known() {
+			assert_eq!(FileType::from(u16::from(it)), it);
+		}
+		for &it in Machine::all_known() {
+			assert_eq!(Machine::from(u16::from(it)), it);
+		}
+		for value in 0..=u16::MAX {
+			assert_eq!(u16::from(FileType::from(value)), value);
+			assert_eq!(u16::from(Machine::from(value)), value);
+		}
+	}
+
+	#[test]
+	fn header_table_enums_round_trip() {
+		for &it in ProgramType::all_known() {
+			assert_eq!(ProgramType::from(u32::from(it)), it);
+		}
+		for &it in SectionType::all_known() {
+			assert_eq!(SectionType::from(u32::from(it)), it);
+		}
+		assert_eq!(
+			SectionType::from(u32::from(SectionType::MipsAbiFlags)).for_machine(Machine::Mips),
+			SectionType::MipsAbiFlags
+		);
+
+		// every range boundary, plus a stride through the rest
+		let known = ProgramType::all_known()
+			.iter()
+			.map(|&it| u32::from(it))
+			.chain(SectionType::all_known().iter().map(|&it| u32::from(it)));
+		let values = known
+			.flat_map(|it| [it.wrapping_sub(1), it, it.wrapping_add(1)])
+			.chain([
+				0x5FFF_FFFF,
+				0x6000_0000,
+				0x6FFF_FFFF,
+				0x7000_0000,
+				0x7FFF_FFFF,
+				0x8000_0000,
+			])
+			.chain((0..=u32::MAX).step_by(0x10_0001));
+		for value in values {
+			assert_eq!(u32::from(ProgramType::from(value)), value);
+			assert_eq!(u32::from(SectionType::from(value)), value);
+		}
+	}
+
+	#[test]
+	fn extended_program_header_count_is_read_from_section_zero() {
+		let count = fixture("sample").segments().len();
+
+		let mut bytes = fixture_bytes("sample");
+		write_u16_at(&mut bytes, 0x38, PN_XNUM);
+		let first = section_header_at(&bytes, 0);
+		// `sh_info`
+		write_u32_at(&mut bytes, first + 0x2C, count as u32);
+
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+		assert_eq!(elf.header.program_header_count, PN_XNUM);
+		assert_eq!(elf.segments().len(), count);
+		assert!(elf.segments().iter().any(Segment::is_loadable));
+	}
+
+	#[test]
+	fn extended_program_header_count_has_to_fit_in_the_file() {
+		let mut bytes = fixture_bytes("sample");
+		write_u16_at(&mut bytes, 0x38, PN_XNUM);
+		let first = section_header_at(&bytes, 0);
+
+		for count in [u32::MAX, bytes.len() as u32 / 56] {
+			write_u32_at(&mut bytes, first + 0x2C, count);
+			let error = Elf::try_from(bytes.as_slice()).err().unwrap();
+			assert_eq!(error.kind(), &ErrorKind::Malformed);
+			assert_eq!(
+				error.to_string(),
+				format!("Extended program header count {count} doesn't fit in the file")
+			);
+		}
+	}
+
+	#[test]
+	fn extended_program_header_count_needs_section_headers() {
+		let mut bytes = fixture_bytes("sample");
+		write_u16_at(&mut bytes, 0x38, PN_XNUM);
+		write_u64_at(&mut bytes, 0x28, 0);
+
+		let error = Elf::try_from(bytes.as_slice()).err().unwrap();
+		assert_eq!(error.kind(), &ErrorKind::Malformed);
+	}
+
+	#[test]
+	fn big_endian_files_parse_end_to_end() {
+		let elf = fixture("mips64-be.o");
+		let header = &elf.header;
+
+		assert_eq!(header.ident.magic, fixture("sample").header.ident.magic);
+		assert!(header.ident.is_64_bit);
+		assert!(!header.ident.is_little_endian);
+		assert_eq!(header.kind, FileType::Relocatable);
+		assert_eq!(header.machine, Machine::Mips);
+		assert_eq!(header.version, 1);
+		assert_eq!(header.flags, 0x6000_0005);
+		assert_eq!(header.header_size, 64);
+		assert_eq!(header.section_header_entry_size, 64);
+		assert_eq!(header.section_header_count, 8);
+		assert_eq!(header.section_header_names_index, 1);
+
+		let text = elf.section_by_name(".text").unwrap().unwrap();
+		assert_eq!(text.offset, 0x40);
+		assert_eq!(text.size, 12);
+		assert_eq!(text.address_alignment, 16);
+		// `jr $ra`
+		assert_eq!(elf.section_data(text).unwrap()[..4], [0x03, 0xE0, 0x00, 0x08]);
+		let abi_flags = elf.section_by_name(".MIPS.abiflags").unwrap().unwrap();
+		assert_eq!(abi_flags.kind, SectionType::MipsAbiFlags);
+
+		let symbols = elf.symbols().unwrap();
+		let answer = symbols.iter().find(|it| it.name == "answer").unwrap();
+		assert_eq!(answer.kind, SymbolType::Function);
+		assert_eq!(answer.size, 12);
+		assert_eq!(answer.section_index, 2);
+		let value = symbols.iter().find(|it| it.name == "value").unwrap();
+		assert_eq!(value.kind, SymbolType::Object);
+		assert_eq!(value.size, 8);
+		let data = elf.section_by_name(".data").unwrap().unwrap();
+		assert_eq!(elf.section_data(data).unwrap(), 0x0102_0304_0506_0708u64.to_be_bytes());
+
+		let flags = elf.mips_abi_flags().unwrap().unwrap();
+		assert_eq!(flags.isa_level, 64);
+		assert_eq!(flags.gpr_size, 2);
+	}
+
+	#[test]
+	fn entry_must_point_into_an_executable_segment() {
+		let elf = fixture("sample");
+		let text = elf.first_executable_segment().unwrap();
+		assert!(text.memory_range().contains(&elf.header.entry));
+		assert!(elf.entry_is_valid());
+
+		let data = elf.section_by_name(".data").unwrap().unwrap().address;
+		for entry in [data, text.memory_range().end, 0] {
+			let mut bytes = fixture_bytes("sample");
+			write_u64_at(&mut bytes, 0x18, entry);
+
+			let elf = Elf::try_from(bytes.as_slice()).unwrap();
+			assert!(!elf.entry_is_valid(), "{entry:#x}");
+		}
+	}
+
+	#[test]
+	fn truncated_files_say_how_much_was_missing() {
+		let bytes = fixture_bytes("sample");
+		let eof = |length: usize| {
+			let error = Elf::try_from(&bytes[..length]).err().unwrap();
+			match *error.kind() {
+				ErrorKind::UnexpectedEof {
+					offset,
+					wanted,
+					available,
+				} => (offset, wanted, available),
+				_ => panic!("{error}"),
+			}
+		};
+
+		// in the middle of `e_flags`
+		assert_eq!(eof(0x32), (0x30, 4, 2));
+		// the section header table is read in one go
+		let table = section_header_at(&bytes, 0);
+		let table_size = bytes.len() - table;
+		assert_eq!(
+			eof(bytes.len() - 10),
+			(table as u64, table_size as u64, table_size as u64 - 10)
+		);
+	}
+
+	#[test]
+	fn header_tables_are_checked_before_they_are_read() {
+		let bytes = fixture_bytes("sample");
+		let eof = |count_offset: usize, table_offset: usize, entry_size: u64| {
+			let mut bytes = bytes.clone();
+			write_u16_at(&mut bytes, count_offset, 0xFFFE);
+			let table = read_u64_at(&bytes, table_offset);
+
+			let error = Elf::try_from(bytes.as_slice()).err().unwrap();
+			let ErrorKind::UnexpectedEof {
+				offset,
+				wanted,
+				available,
+			} = *error.kind()
+			else {
+				panic!("{error}");
+			};
+			assert_eq!((offset, wanted), (table, 0xFFFE * entry_size));
+			assert_eq!(available, bytes.len() as u64 - table);
+		};
+
+		// `e_phnum` and `e_shnum`
+		eof(0x38, 0x20, 56);
+		eof(0x3C, 0x28, 64);
+	}
+
+	#[test]
+	fn parsing_from_bytes_matches_the_file() {
+		let from_file = fixture("sample");
+		let from_bytes = {
+			let bytes = fixture_bytes("sample");
+			Elf::try_from(bytes.as_slice()).unwrap()
+		};
+
+		assert_eq!(from_bytes.header.entry, from_file.header.entry);
+		assert_eq!(from_bytes.segments.len(), from_file.segments.len());
+		assert_eq!(from_bytes.sections.len(), from_file.sections.len());
+		// bodies are read lazily from the copy, after the original bytes are gone
+		for (ours, theirs) in from_bytes.sections.iter().zip(&from_file.sections) {
+			assert_eq!(
+				from_bytes.section_name(ours).unwrap(),
+				from_file.section_name(theirs).unwrap()
+			);
+			assert_eq!(
+				from_bytes.section_data(ours).unwrap(),
+				from_file.section_data(theirs).unwrap()
+			);
+		}
+
+		let error = Elf::try_from(&b"#!/bin/sh\n"[..]).err().unwrap();
+		assert_eq!(error.to_string(), "File format is not ELF64!");
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn parse_lazy_never_loads_bodies() {
+		let options = ParseOptions {
+			load_segments: true,
+			load_sections: true,
+			..ParseOptions::default()
+		};
+		let open = || File::open(fixture_path("sample")).unwrap();
+
+		for elf in [
+			Elf::parse_lazy(open()),
+			Parser::with_options(options).parse_lazy(open()),
+		] {
+			let elf = elf.unwrap();
+			assert!(elf.segments.iter().all(|it| it.data.get().is_none()));
+			assert!(elf.sections.iter().all(|it| !it.is_loaded()));
+		}
+	}
+
+	// a timing, not a check, run with `cargo test --release -- --ignored --nocapture parser_reuse`
+	#[test]
+	#[ignore]
+	fn parser_reuse_timing() {
+		const ROUNDS: u32 = 20_000;
+		let files: Vec<&'static [u8]> = ["sample", "sample-debug", "hardened", "sample.o"]
+			.into_iter()
+			.map(|it| &*fixture_bytes(it).leak())
+			.collect();
+
+		let start = std::time::Instant::now();
+		for _ in 0..ROUNDS {
+			for &bytes in &files {
+				Elf::parse_reader(io::Cursor::new(bytes)).unwrap();
+			}
+		}
+		let fresh = start.elapsed();
+
+		let mut parser = Parser::new();
+		let start = std::time::Instant::now();
+		for _ in 0..ROUNDS {
+			for &bytes in &files {
+				parser.parse_reader(io::Cursor::new(bytes)).unwrap();
+			}
+		}
+		let reused = start.elapsed();
+
+		let per_parse = |it: std::time::Duration| it / (ROUNDS * files.len() as u32);
+		println!("new parser per file: {:?} per parse", per_parse(fresh));
+		println!("one reused parser:   {:?} per parse", per_parse(reused));
+	}
+}
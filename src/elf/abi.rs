@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use super::{
+	version::VERSION_INDEX_MASK, Elf, Result, SectionType, SymbolBinding, SymbolType,
+	SymbolVisibility,
+};
+use crate::consts::{SHN_ABS, SHN_UNDEF, STB_GNU_UNIQUE};
+
+// version indices 0 and 1 are the local and the unversioned global version
+const FIRST_DEFINED_VERSION: u16 = 2;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AbiSymbol {
+	pub name: String,
+	pub version: Option<String>,
+	pub binding: SymbolBinding,
+	pub kind: SymbolType,
+}
+
+#[derive(Debug)]
+pub struct AbiChange {
+	pub old: AbiSymbol,
+	pub new: AbiSymbol,
+}
+
+impl AbiChange {
+	// other code may rely on the symbol not being overridable, or on what kind of thing it is
+	pub fn is_breaking(&self) -> bool {
+		self.old.kind != self.new.kind
+			|| self.old.binding == SymbolBinding::Global && self.new.binding == SymbolBinding::Weak
+	}
+}
+
+// symbols are matched by name and version, so a symbol moving to a new version shows up as removed
+// from the old one
+#[derive(Debug, Default)]
+pub struct AbiDiff {
+	pub removed: Vec<AbiSymbol>,
+	pub added: Vec<AbiSymbol>,
+	pub changed: Vec<AbiChange>,
+}
+
+impl AbiDiff {
+	// adding symbols is fine, anything linked against the old library still finds what it needs
+	pub fn is_breaking(&self) -> bool {
+		!self.removed.is_empty() || self.changed.iter().any(AbiChange::is_breaking)
+	}
+}
+
+impl Elf {
+	// `self` is the new version of the library
+	pub fn compare_abi(&self, old: &Elf) -> Result<AbiDiff> {
+		let mut old_symbols: HashMap<_, _> = old
+			.abi_symbols()?
+			.into_iter()
+			.map(|it| ((it.name.clone(), it.version.clone()), it))
+			.collect();
+
+		let mut diff = AbiDiff::default();
+		for new in self.abi_symbols()? {
+			match old_symbols.remove(&(new.name.clone(), new.version.clone())) {
+				Some(old) if old.binding != new.binding || old.kind != new.kind => {
+					diff.changed.push(AbiChange { old, new });
+				}
+				Some(_) => {}
+				None => diff.added.push(new),
+			}
+		}
+		diff.removed = old_symbols.into_values().collect();
+
+		diff.removed.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+		diff.added.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+		diff.changed
+			.sort_by(|a, b| (&a.new.name, &a.new.version).cmp(&(&b.new.name, &b.new.version)));
+		Ok(diff)
+	}
+
+	// the defined dynamic symbols other components can link against
+	fn abi_symbols(&self) -> Result<Vec<AbiSymbol>> {
+		let version_names: HashMap<_, _> =
+			self.version_definitions()?.into_iter().map(|it| (it.index, it.name)).collect();
+
+		let mut exported = Vec::new();
+		for (index, table) in self.sections.iter().enumerate() {
+			if table.kind != SectionType::LinkerSymbolTable {
+				continue;
+			}
+
+			let versions = self.symbol_version_indices(index)?;
+			for (position, symbol) in self.section_symbols(table)?.into_iter().enumerate() {
+				let is_global = matches!(
+					symbol.binding,
+					SymbolBinding::Global
+						| SymbolBinding::Weak
+						| SymbolBinding::OperatingSystem(STB_GNU_UNIQUE)
+				);
+				let is_visible = matches!(
+					symbol.visibility(),
+					SymbolVisibility::Default | SymbolVisibility::Protected
+				);
+				if symbol.section_index == SHN_UNDEF || !is_global || !is_visible {
+					continue;
+				}
+
+				let version_index = versions
+					.as_ref()
+					.and_then(|it| it.get(position))
+					.map(|it| it & VERSION_INDEX_MASK);
+				// the local version hides the symbol even though its binding says otherwise
+				if version_index == Some(0) {
+					continue;
+				}
+				let version = version_index
+					.filter(|&it| it >= FIRST_DEFINED_VERSION)
+					.and_then(|it| version_names.get(&it).cloned());
+				// the linker defines an absolute symbol named after each version
+				if symbol.section_index == SHN_ABS && version.as_ref() == Some(&symbol.name) {
+					continue;
+				}
+
+				exported.push(AbiSymbol {
+					name: symbol.name,
+					version,
+					binding: symbol.binding,
+					kind: symbol.kind,
+				});
+			}
+		}
+
+		Ok(exported)
+	}
+}
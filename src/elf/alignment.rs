@@ -0,0 +1,64 @@
+use super::{Elf, SectionType};
+
+#[derive(Debug, PartialEq)]
+pub enum AlignmentIssue {
+	SegmentOffset {
+		index: usize,
+		offset: u64,
+		virtual_address: u64,
+		alignment: u64,
+	},
+	SectionAddress {
+		index: usize,
+		address: u64,
+		alignment: u64,
+	},
+	SectionOffset {
+		index: usize,
+		offset: u64,
+		alignment: u64,
+	},
+}
+
+impl Elf {
+	pub fn check_alignment(&self) -> Vec<AlignmentIssue> {
+		let mut issues = Vec::new();
+
+		// segments are mapped page by page, so the file offset has to line up with the address
+		for (index, segment) in self.segments.iter().enumerate() {
+			let alignment = segment.alignment;
+			if alignment > 1 && segment.offset % alignment != segment.virtual_address % alignment {
+				issues.push(AlignmentIssue::SegmentOffset {
+					index,
+					offset: segment.offset,
+					virtual_address: segment.virtual_address,
+					alignment,
+				});
+			}
+		}
+
+		for (index, section) in self.sections.iter().enumerate() {
+			let alignment = section.address_alignment;
+			if alignment <= 1 {
+				continue;
+			}
+			if section.address % alignment != 0 {
+				issues.push(AlignmentIssue::SectionAddress {
+					index,
+					address: section.address,
+					alignment,
+				});
+			}
+			// `NoBits` sections take no space in the file, so their offset is meaningless
+			if section.kind != SectionType::NoBits && section.offset % alignment != 0 {
+				issues.push(AlignmentIssue::SectionOffset {
+					index,
+					offset: section.offset,
+					alignment,
+				});
+			}
+		}
+
+		issues
+	}
+}
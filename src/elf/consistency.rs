@@ -0,0 +1,74 @@
+use super::{Elf, Machine, Warning};
+
+impl Machine {
+	// whether files for this machine are 64-bit and little-endian, `None` where both are in use
+	// (e.g. big-endian ARM or 32-bit MIPS running on 64-bit cores)
+	fn expected_profile(&self) -> (Option<bool>, Option<bool>) {
+		match self {
+			Machine::X86 => (Some(false), Some(true)),
+			// the x32 ABI is the exception, but it's ELF32 which isn't parsed anyway
+			Machine::Amd64 => (Some(true), Some(true)),
+			Machine::Arm => (Some(false), None),
+			Machine::Arm64 => (Some(true), None),
+			// RV32 is ELF32 and big-endian RISC-V isn't used in practice
+			Machine::RiscV => (Some(true), Some(true)),
+			Machine::Mips | Machine::Other(_) => (None, None),
+		}
+	}
+}
+
+impl Elf {
+	// mismatches are a sign of a corrupt or crafted header, but don't keep the file from parsing
+	pub fn validate_machine_consistency(&self) -> Vec<Warning> {
+		let machine = self.header.machine;
+		let is_64_bit = self.header.ident.is_64_bit;
+		let is_little_endian = self.header.ident.is_little_endian;
+
+		let (expected_64_bit, expected_little_endian) = machine.expected_profile();
+		let mut warnings = Vec::new();
+
+		if expected_64_bit.is_some_and(|it| it != is_64_bit) {
+			warnings.push(Warning::MachineClassMismatch { machine, is_64_bit });
+		}
+		if expected_little_endian.is_some_and(|it| it != is_little_endian) {
+			warnings.push(Warning::MachineEndiannessMismatch {
+				machine,
+				is_little_endian,
+			});
+		}
+
+		warnings
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::testing::{fixture_bytes, write_u16_at};
+
+	const E_MACHINE_OFFSET: usize = 0x12;
+
+	#[test]
+	fn risc_v_files_are_64_bit_little_endian() {
+		let mut bytes = fixture_bytes("sample");
+		write_u16_at(&mut bytes, E_MACHINE_OFFSET, u16::from(Machine::RiscV));
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		assert_eq!(elf.header.machine, Machine::RiscV);
+		assert!(elf.validate_machine_consistency().is_empty());
+
+		let mut bytes = fixture_bytes("mips64-be.o");
+		bytes[E_MACHINE_OFFSET..E_MACHINE_OFFSET + 2]
+			.copy_from_slice(&u16::from(Machine::RiscV).to_be_bytes());
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		let warnings = elf.validate_machine_consistency();
+		assert!(matches!(
+			warnings.as_slice(),
+			[Warning::MachineEndiannessMismatch {
+				machine: Machine::RiscV,
+				is_little_endian: false,
+			}]
+		));
+	}
+}
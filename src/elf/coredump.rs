@@ -0,0 +1,66 @@
+use super::{Elf, FileType, Machine, Note, Result};
+use crate::{consts::NT_PRSTATUS, reader::Reader};
+
+// `pr_reg` follows the signal info, pending and held signals, ids and times of `elf_prstatus`
+const PRSTATUS_REGISTERS_OFFSET: usize = 112;
+// `rip` comes after the 16 general purpose registers of `user_regs_struct`
+const AMD64_INSTRUCTION_POINTER_OFFSET: usize = 16 * 8;
+// `pc` comes after `regs[31]` and `sp` of `user_pt_regs`
+const AARCH64_INSTRUCTION_POINTER_OFFSET: usize = 32 * 8;
+
+pub struct CoreDump {
+	pub machine: Machine,
+	pub is_little_endian: bool,
+	// one `NT_PRSTATUS` per thread, the one that caused the dump comes first
+	pub thread_statuses: Vec<Vec<u8>>,
+}
+
+impl CoreDump {
+	pub fn instruction_pointer(&self) -> Option<u64> {
+		let offset = match self.machine {
+			Machine::Amd64 => AMD64_INSTRUCTION_POINTER_OFFSET,
+			Machine::Arm64 => AARCH64_INSTRUCTION_POINTER_OFFSET,
+			_ => return None,
+		};
+
+		let status = self.thread_statuses.first()?;
+		let mut reader = Reader::new(status, self.is_little_endian);
+		reader.seek(PRSTATUS_REGISTERS_OFFSET + offset).ok()?;
+		reader.read_u64().ok()
+	}
+}
+
+impl Elf {
+	pub fn is_core_dump(&self) -> bool {
+		self.header.kind == FileType::Core
+	}
+
+	// taken from the segments, since some tools (e.g. gdb's `gcore`) also write section headers
+	// that only partially cover them
+	pub fn core_notes(&self) -> Result<Vec<Note>> {
+		if !self.is_core_dump() {
+			return Err("File is not a core dump".into());
+		}
+
+		self.segment_notes()
+	}
+
+	pub fn core_dump(&self) -> Result<Option<CoreDump>> {
+		if !self.is_core_dump() {
+			return Ok(None);
+		}
+
+		let thread_statuses = self
+			.core_notes()?
+			.into_iter()
+			.filter(|it| it.name == "CORE" && it.kind == NT_PRSTATUS)
+			.map(|it| it.description)
+			.collect();
+
+		Ok(Some(CoreDump {
+			machine: self.header.machine,
+			is_little_endian: self.header.ident.is_little_endian,
+			thread_statuses,
+		}))
+	}
+}
@@ -0,0 +1,112 @@
+use super::{Elf, FileHeader, Result};
+
+#[derive(Debug, Default)]
+pub struct ElfDiff {
+	pub header: Vec<HeaderChange>,
+	pub added_sections: Vec<String>,
+	pub removed_sections: Vec<String>,
+	pub resized_sections: Vec<SectionResize>,
+}
+
+#[derive(Debug)]
+pub struct HeaderChange {
+	pub field: &'static str,
+	pub old: u64,
+	pub new: u64,
+}
+
+#[derive(Debug)]
+pub struct SectionResize {
+	pub name: String,
+	pub old_size: u64,
+	pub new_size: u64,
+}
+
+impl ElfDiff {
+	pub fn is_empty(&self) -> bool {
+		self.header.is_empty()
+			&& self.added_sections.is_empty()
+			&& self.removed_sections.is_empty()
+			&& self.resized_sections.is_empty()
+	}
+}
+
+impl Elf {
+	// sections are matched by name, in order for names that appear more than once
+	pub fn diff(&self, other: &Elf) -> Result<ElfDiff> {
+		let mut diff = ElfDiff {
+			header: header_changes(&self.header, &other.header),
+			..ElfDiff::default()
+		};
+
+		let mut other_sections = Vec::with_capacity(other.sections.len());
+		for section in &other.sections {
+			other_sections.push(Some((other.section_name(section)?, section.size)));
+		}
+
+		for section in &self.sections {
+			let name = self.section_name(section)?;
+			let matching =
+				other_sections.iter_mut().find(|it| it.is_some_and(|(it, _)| it == name));
+
+			match matching.and_then(Option::take) {
+				Some((_, new_size)) if new_size != section.size => {
+					diff.resized_sections.push(SectionResize {
+						name: name.to_owned(),
+						old_size: section.size,
+						new_size,
+					})
+				}
+				Some(_) => {}
+				None => diff.removed_sections.push(name.to_owned()),
+			}
+		}
+
+		diff.added_sections =
+			other_sections.into_iter().flatten().map(|(it, _)| it.to_owned()).collect();
+
+		Ok(diff)
+	}
+}
+
+fn header_changes(old: &FileHeader, new: &FileHeader) -> Vec<HeaderChange> {
+	let fields = [
+		("magic", old.ident.magic.into(), new.ident.magic.into()),
+		("class", old.ident.is_64_bit.into(), new.ident.is_64_bit.into()),
+		("data", old.ident.is_little_endian.into(), new.ident.is_little_endian.into()),
+		("ident_version", old.ident.version.into(), new.ident.version.into()),
+		("os_abi", u8::from(old.ident.os_abi).into(), u8::from(new.ident.os_abi).into()),
+		("abi_version", old.ident.abi_version.into(), new.ident.abi_version.into()),
+		("type", u16::from(old.kind).into(), u16::from(new.kind).into()),
+		("machine", u16::from(old.machine).into(), u16::from(new.machine).into()),
+		("version", old.version.into(), new.version.into()),
+		("entry", old.entry, new.entry),
+		("program_header_offset", old.program_header_offset, new.program_header_offset),
+		("section_header_offset", old.section_header_offset, new.section_header_offset),
+		("flags", old.flags.into(), new.flags.into()),
+		("header_size", old.header_size.into(), new.header_size.into()),
+		(
+			"program_header_entry_size",
+			old.program_header_entry_size.into(),
+			new.program_header_entry_size.into(),
+		),
+		("program_header_count", old.program_header_count.into(), new.program_header_count.into()),
+		(
+			"section_header_entry_size",
+			old.section_header_entry_size.into(),
+			new.section_header_entry_size.into(),
+		),
+		("section_header_count", old.section_header_count.into(), new.section_header_count.into()),
+		(
+			"section_header_names_index",
+			old.section_header_names_index.into(),
+			new.section_header_names_index.into(),
+		),
+	];
+
+	fields
+		.into_iter()
+		.filter(|(_, old, new)| old != new)
+		.map(|(field, old, new)| HeaderChange { field, old, new })
+		.collect()
+}
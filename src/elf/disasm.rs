@@ -0,0 +1,48 @@
+use super::{Elf, Machine, Result, SymbolType};
+use crate::consts::SHN_UNDEF;
+
+// everything a disassembler needs to decode the bytes on its own
+#[derive(Debug, PartialEq)]
+pub struct DisasmContext {
+	pub bytes: Vec<u8>,
+	pub address: u64,
+	pub machine: Machine,
+	pub is_64_bit: bool,
+	pub is_little_endian: bool,
+}
+
+impl Elf {
+	pub fn disasm_context_at(&self, address: u64, size: usize) -> Result<DisasmContext> {
+		Ok(DisasmContext {
+			bytes: self.read_virtual(address, size)?,
+			address,
+			machine: self.header.machine,
+			is_64_bit: self.header.ident.is_64_bit,
+			is_little_endian: self.header.ident.is_little_endian,
+		})
+	}
+
+	pub fn entry_disassembly_context(&self, size: usize) -> Result<DisasmContext> {
+		self.disasm_context_at(self.canonical_entry(), size)
+	}
+
+	// covers the whole function, so there's no size to pass
+	pub fn disasm_context_for_symbol(&self, name: &str) -> Result<Option<DisasmContext>> {
+		let symbol = self.symbols()?.into_iter().find(|it| {
+			it.name == name && it.kind == SymbolType::Function && it.section_index != SHN_UNDEF
+		});
+		let Some(symbol) = symbol else {
+			return Ok(None);
+		};
+
+		// like the entry point, Thumb functions have their lowest bit set
+		let address = match self.header.machine {
+			Machine::Arm => symbol.value & !1,
+			_ => symbol.value,
+		};
+		let size = usize::try_from(symbol.size)
+			.map_err(|_| format!("Symbol {name} is too large to disassemble"))?;
+
+		self.disasm_context_at(address, size).map(Some)
+	}
+}
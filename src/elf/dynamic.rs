@@ -0,0 +1,545 @@
+use std::path::{Path, PathBuf};
+
+use super::{read_string, Elf, Machine, ProgramType, Result, Section, SectionType};
+use crate::{
+	consts::{
+		DF_1_DIRECT, DF_1_GLOBAL, DF_1_GROUP, DF_1_INITFIRST, DF_1_INTERPOSE, DF_1_LOADFLTR,
+		DF_1_NODEFLIB, DF_1_NODELETE, DF_1_NODUMP, DF_1_NOOPEN, DF_1_NOW, DF_1_ORIGIN, DF_1_PIE,
+		DF_BIND_NOW, DF_ORIGIN, DF_STATIC_TLS, DF_SYMBOLIC, DF_TEXTREL,
+	},
+	error::Error,
+};
+
+pub struct DynamicEntry {
+	pub tag: DynamicTag,
+	pub value: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DynamicTag {
+	Null,
+	Needed,
+	PltRelocationSize,
+	PltGot,
+	Hash,
+	StringTable,
+	SymbolTable,
+	RelocationsWithAddends,
+	RelocationsWithAddendsSize,
+	RelocationWithAddendEntrySize,
+	StringTableSize,
+	SymbolEntrySize,
+	Init,
+	Finish,
+	SharedObjectName,
+	RuntimePath,
+	Symbolic,
+	Relocations,
+	RelocationsSize,
+	RelocationEntrySize,
+	PltRelocationType,
+	Debug,
+	TextRelocations,
+	PltRelocations,
+	BindNow,
+	InitArray,
+	FinishArray,
+	InitArraySize,
+	FinishArraySize,
+	RunPath,
+	Flags,
+	PreInitArray,
+	PreInitArraySize,
+	RelrSize,
+	Relr,
+	RelrEntrySize,
+	GnuHash,
+	VersionSymbols,
+	Flags1,
+	VersionDefinitions,
+	VersionDefinitionCount,
+	VersionNeeded,
+	VersionNeededCount,
+	OperatingSystem(u64),
+	Processor(u64),
+	Other(u64),
+}
+
+impl DynamicTag {
+	pub fn all_known() -> &'static [Self] {
+		&[
+			Self::Null,
+			Self::Needed,
+			Self::PltRelocationSize,
+			Self::PltGot,
+			Self::Hash,
+			Self::StringTable,
+			Self::SymbolTable,
+			Self::RelocationsWithAddends,
+			Self::RelocationsWithAddendsSize,
+			Self::RelocationWithAddendEntrySize,
+			Self::StringTableSize,
+			Self::SymbolEntrySize,
+			Self::Init,
+			Self::Finish,
+			Self::SharedObjectName,
+			Self::RuntimePath,
+			Self::Symbolic,
+			Self::Relocations,
+			Self::RelocationsSize,
+			Self::RelocationEntrySize,
+			Self::PltRelocationType,
+			Self::Debug,
+			Self::TextRelocations,
+			Self::PltRelocations,
+			Self::BindNow,
+			Self::InitArray,
+			Self::FinishArray,
+			Self::InitArraySize,
+			Self::FinishArraySize,
+			Self::RunPath,
+			Self::Flags,
+			Self::PreInitArray,
+			Self::PreInitArraySize,
+			Self::RelrSize,
+			Self::Relr,
+			Self::RelrEntrySize,
+			Self::GnuHash,
+			Self::VersionSymbols,
+			Self::Flags1,
+			Self::VersionDefinitions,
+			Self::VersionDefinitionCount,
+			Self::VersionNeeded,
+			Self::VersionNeededCount,
+		]
+	}
+}
+
+impl From<u64> for DynamicTag {
+	fn from(value: u64) -> Self {
+		match value {
+			0x00 => Self::Null,
+			0x01 => Self::Needed,
+			0x02 => Self::PltRelocationSize,
+			0x03 => Self::PltGot,
+			0x04 => Self::Hash,
+			0x05 => Self::StringTable,
+			0x06 => Self::SymbolTable,
+			0x07 => Self::RelocationsWithAddends,
+			0x08 => Self::RelocationsWithAddendsSize,
+			0x09 => Self::RelocationWithAddendEntrySize,
+			0x0A => Self::StringTableSize,
+			0x0B => Self::SymbolEntrySize,
+			0x0C => Self::Init,
+			0x0D => Self::Finish,
+			0x0E => Self::SharedObjectName,
+			0x0F => Self::RuntimePath,
+			0x10 => Self::Symbolic,
+			0x11 => Self::Relocations,
+			0x12 => Self::RelocationsSize,
+			0x13 => Self::RelocationEntrySize,
+			0x14 => Self::PltRelocationType,
+			0x15 => Self::Debug,
+			0x16 => Self::TextRelocations,
+			0x17 => Self::PltRelocations,
+			0x18 => Self::BindNow,
+			0x19 => Self::InitArray,
+			0x1A => Self::FinishArray,
+			0x1B => Self::InitArraySize,
+			0x1C => Self::FinishArraySize,
+			0x1D => Self::RunPath,
+			0x1E => Self::Flags,
+			0x20 => Self::PreInitArray,
+			0x21 => Self::PreInitArraySize,
+			0x23 => Self::RelrSize,
+			0x24 => Self::Relr,
+			0x25 => Self::RelrEntrySize,
+			0x6FFF_FEF5 => Self::GnuHash,
+			0x6FFF_FFF0 => Self::VersionSymbols,
+			0x6FFF_FFFB => Self::Flags1,
+			0x6FFF_FFFC => Self::VersionDefinitions,
+			0x6FFF_FFFD => Self::VersionDefinitionCount,
+			0x6FFF_FFFE => Self::VersionNeeded,
+			0x6FFF_FFFF => Self::VersionNeededCount,
+			0x6000_000D..=0x6FFF_FFFF => Self::OperatingSystem(value),
+			0x7000_0000..=0x7FFF_FFFF => Self::Processor(value),
+			_ => Self::Other(value),
+		}
+	}
+}
+
+impl From<DynamicTag> for u64 {
+	fn from(tag: DynamicTag) -> Self {
+		match tag {
+			DynamicTag::Null => 0x00,
+			DynamicTag::Needed => 0x01,
+			DynamicTag::PltRelocationSize => 0x02,
+			DynamicTag::PltGot => 0x03,
+			DynamicTag::Hash => 0x04,
+			DynamicTag::StringTable => 0x05,
+			DynamicTag::SymbolTable => 0x06,
+			DynamicTag::RelocationsWithAddends => 0x07,
+			DynamicTag::RelocationsWithAddendsSize => 0x08,
+			DynamicTag::RelocationWithAddendEntrySize => 0x09,
+			DynamicTag::StringTableSize => 0x0A,
+			DynamicTag::SymbolEntrySize => 0x0B,
+			DynamicTag::Init => 0x0C,
+			DynamicTag::Finish => 0x0D,
+			DynamicTag::SharedObjectName => 0x0E,
+			DynamicTag::RuntimePath => 0x0F,
+			DynamicTag::Symbolic => 0x10,
+			DynamicTag::Relocations => 0x11,
+			DynamicTag::RelocationsSize => 0x12,
+			DynamicTag::RelocationEntrySize => 0x13,
+			DynamicTag::PltRelocationType => 0x14,
+			DynamicTag::Debug => 0x15,
+			DynamicTag::TextRelocations => 0x16,
+			DynamicTag::PltRelocations => 0x17,
+			DynamicTag::BindNow => 0x18,
+			DynamicTag::InitArray => 0x19,
+			DynamicTag::FinishArray => 0x1A,
+			DynamicTag::InitArraySize => 0x1B,
+			DynamicTag::FinishArraySize => 0x1C,
+			DynamicTag::RunPath => 0x1D,
+			DynamicTag::Flags => 0x1E,
+			DynamicTag::PreInitArray => 0x20,
+			DynamicTag::PreInitArraySize => 0x21,
+			DynamicTag::RelrSize => 0x23,
+			DynamicTag::Relr => 0x24,
+			DynamicTag::RelrEntrySize => 0x25,
+			DynamicTag::GnuHash => 0x6FFF_FEF5,
+			DynamicTag::VersionSymbols => 0x6FFF_FFF0,
+			DynamicTag::Flags1 => 0x6FFF_FFFB,
+			DynamicTag::VersionDefinitions => 0x6FFF_FFFC,
+			DynamicTag::VersionDefinitionCount => 0x6FFF_FFFD,
+			DynamicTag::VersionNeeded => 0x6FFF_FFFE,
+			DynamicTag::VersionNeededCount => 0x6FFF_FFFF,
+			DynamicTag::OperatingSystem(value) => value,
+			DynamicTag::Processor(value) => value,
+			DynamicTag::Other(value) => value,
+		}
+	}
+}
+
+impl Elf {
+	pub fn dynamic_entries(&self) -> Result<Vec<DynamicEntry>> {
+		// relocatable objects can have a `.dynamic` section without a `PT_DYNAMIC` segment
+		let data = match self.dynamic_section() {
+			Some(section) => self.section_data(section)?,
+			None => match self.segment_data_of_type(ProgramType::Dynamic)? {
+				Some(data) => data,
+				None => return Ok(Vec::new()),
+			},
+		};
+
+		let mut entries = Vec::new();
+		let mut reader = self.reader_for(data);
+
+		while !reader.is_empty() {
+			let tag = reader.read_u64().map(DynamicTag::from)?;
+			let value = reader.read_u64()?;
+
+			if tag == DynamicTag::Null {
+				break;
+			}
+
+			entries.push(DynamicEntry { tag, value });
+		}
+
+		Ok(entries)
+	}
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct DynamicFlags {
+	pub flags: u64,
+	pub flags_1: u64,
+	pub origin: bool,
+	pub symbolic: bool,
+	pub text_relocations: bool,
+	pub bind_now: bool,
+	pub static_tls: bool,
+	pub now: bool,
+	pub global: bool,
+	pub group: bool,
+	pub nodelete: bool,
+	pub load_filter: bool,
+	pub init_first: bool,
+	pub noopen: bool,
+	pub direct: bool,
+	pub interpose: bool,
+	pub nodeflib: bool,
+	pub nodump: bool,
+	pub pie: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LibcFlavor {
+	Glibc,
+	Musl,
+	Unknown,
+}
+
+impl Elf {
+	pub fn dynamic_flags(&self) -> Result<DynamicFlags> {
+		let entries = self.dynamic_entries()?;
+
+		let flags = find_dynamic_value(&entries, DynamicTag::Flags).unwrap_or(0);
+		let flags_1 = find_dynamic_value(&entries, DynamicTag::Flags1).unwrap_or(0);
+
+		// older linkers signal `BIND_NOW` with its own tag instead of the flag
+		let has_bind_now_tag = entries.iter().any(|it| it.tag == DynamicTag::BindNow);
+
+		Ok(DynamicFlags {
+			flags,
+			flags_1,
+			origin: flags & DF_ORIGIN != 0 || flags_1 & DF_1_ORIGIN != 0,
+			symbolic: flags & DF_SYMBOLIC != 0,
+			text_relocations: flags & DF_TEXTREL != 0,
+			bind_now: flags & DF_BIND_NOW != 0 || has_bind_now_tag,
+			static_tls: flags & DF_STATIC_TLS != 0,
+			now: flags_1 & DF_1_NOW != 0,
+			global: flags_1 & DF_1_GLOBAL != 0,
+			group: flags_1 & DF_1_GROUP != 0,
+			nodelete: flags_1 & DF_1_NODELETE != 0,
+			load_filter: flags_1 & DF_1_LOADFLTR != 0,
+			init_first: flags_1 & DF_1_INITFIRST != 0,
+			noopen: flags_1 & DF_1_NOOPEN != 0,
+			direct: flags_1 & DF_1_DIRECT != 0,
+			interpose: flags_1 & DF_1_INTERPOSE != 0,
+			nodeflib: flags_1 & DF_1_NODEFLIB != 0,
+			nodump: flags_1 & DF_1_NODUMP != 0,
+			pie: flags_1 & DF_1_PIE != 0,
+		})
+	}
+
+	pub fn interpreter(&self) -> Result<Option<&str>> {
+		let Some(data) = self.segment_data_of_type(ProgramType::Interpreter)? else {
+			return Ok(None);
+		};
+
+		read_string(data, 0).map(Some)
+	}
+
+	pub fn needed_libraries(&self) -> Result<Vec<String>> {
+		self.dynamic_strings(DynamicTag::Needed)
+	}
+
+	pub fn rpath(&self) -> Result<Vec<String>> {
+		Ok(split_paths(self.dynamic_strings(DynamicTag::RuntimePath)?))
+	}
+
+	pub fn runpath(&self) -> Result<Vec<String>> {
+		Ok(split_paths(self.dynamic_strings(DynamicTag::RunPath)?))
+	}
+
+	// the directories the loader searches for `DT_NEEDED` libraries before the system defaults,
+	// with `origin` being the directory containing this file. `$LIB` expands like glibc does on
+	// Fedora and similar, to `lib64` for 64-bit files and `lib` for 32-bit ones. Debian's
+	// multiarch directories aren't guessed
+	pub fn search_paths(&self, origin: &Path) -> Result<Vec<PathBuf>> {
+		// `DT_RPATH` is ignored as soon as there's a `DT_RUNPATH`
+		let runpath = self.runpath()?;
+		let paths = if runpath.is_empty() {
+			self.rpath()?
+		} else {
+			runpath
+		};
+
+		let origin = origin.to_string_lossy();
+		let platform = match self.header.machine {
+			Machine::Amd64 => Some("x86_64"),
+			Machine::Arm64 => Some("aarch64"),
+			_ => None,
+		};
+		let tokens = [
+			("ORIGIN", Some(origin.as_ref())),
+			(
+				"LIB",
+				Some(if self.header.ident.is_64_bit {
+					"lib64"
+				} else {
+					"lib"
+				}),
+			),
+			("PLATFORM", platform),
+		];
+
+		let mut resolved = Vec::new();
+		for path in paths {
+			// like the loader, drop paths with tokens that can't be expanded
+			let Some(path) = expand_tokens(&path, &tokens).map(PathBuf::from) else {
+				continue;
+			};
+			if !resolved.contains(&path) {
+				resolved.push(path);
+			}
+		}
+
+		Ok(resolved)
+	}
+
+	// a heuristic based on the loader and libc names the two implementations use
+	pub fn libc_flavor(&self) -> Option<LibcFlavor> {
+		let interpreter = self.interpreter().ok().flatten();
+		let needed = self.needed_libraries().unwrap_or_default();
+
+		if interpreter.is_none() && needed.is_empty() {
+			return None;
+		}
+
+		let is_musl = interpreter.is_some_and(|it| it.contains("ld-musl-"))
+			|| needed.iter().any(|it| it.starts_with("libc.musl-"));
+		let is_glibc = interpreter.is_some_and(|it| it.contains("ld-linux"))
+			|| needed.iter().any(|it| it == "libc.so.6");
+
+		match (is_glibc, is_musl) {
+			(true, false) => Some(LibcFlavor::Glibc),
+			(false, true) => Some(LibcFlavor::Musl),
+			_ => Some(LibcFlavor::Unknown),
+		}
+	}
+
+	fn dynamic_strings(&self, tag: DynamicTag) -> Result<Vec<String>> {
+		let entries = self.dynamic_entries()?;
+
+		entries
+			.iter()
+			.filter(|it| it.tag == tag)
+			.map(|it| self.dynamic_string(&entries, it.value).map(str::to_owned))
+			.collect()
+	}
+
+	pub(super) fn dynamic_string(&self, entries: &[DynamicEntry], offset: u64) -> Result<&str> {
+		read_string(self.dynamic_string_table(entries)?, offset as usize)
+	}
+
+	pub(super) fn dynamic_string_table(&self, entries: &[DynamicEntry]) -> Result<&[u8]> {
+		if let Some(section) = self.dynamic_section() {
+			let strings = self
+				.sections
+				.get(section.link as usize)
+				.filter(|it| it.kind == SectionType::StringTable)
+				.ok_or_else(|| {
+					Error::malformed(format!(
+						"Dynamic section links to invalid string table index {}",
+						section.link
+					))
+				})?;
+			return self.section_data(strings);
+		}
+
+		let strings = find_dynamic_value(entries, DynamicTag::StringTable)
+			.ok_or("Dynamic section has no string table")?;
+		let strings_size = find_dynamic_value(entries, DynamicTag::StringTableSize)
+			.ok_or("Dynamic section has no string table size")?;
+
+		self.virtual_data(strings, strings_size)?
+			.ok_or_else(|| format!("Dynamic string table at {strings:#x} is not mapped").into())
+	}
+
+	fn dynamic_section(&self) -> Option<&Section> {
+		self.sections.iter().find(|it| it.kind == SectionType::Dynamic)
+	}
+}
+
+pub(super) fn find_dynamic_value(entries: &[DynamicEntry], tag: DynamicTag) -> Option<u64> {
+	entries.iter().find(|it| it.tag == tag).map(|it| it.value)
+}
+
+// substitutes `$NAME` and `${NAME}`, returning `None` if a known token has no value
+fn expand_tokens(path: &str, tokens: &[(&str, Option<&str>)]) -> Option<String> {
+	let mut expanded = String::with_capacity(path.len());
+	let mut rest = path;
+
+	while let Some(index) = rest.find('$') {
+		expanded.push_str(&rest[..index]);
+		rest = &rest[index + 1..];
+
+		let token = tokens.iter().find_map(|&(name, value)| {
+			if let Some(after) = rest.strip_prefix('{').and_then(|it| it.strip_prefix(name)) {
+				return after.strip_prefix('}').map(|after| (value, after));
+			}
+			// `$LIBRARY` isn't `$LIB` followed by `RARY`
+			let after = rest.strip_prefix(name)?;
+			let is_boundary =
+				!after.starts_with(|it: char| it.is_ascii_alphanumeric() || it == '_');
+			is_boundary.then_some((value, after))
+		});
+
+		match token {
+			Some((value, after)) => {
+				expanded.push_str(value?);
+				rest = after;
+			}
+			None => expanded.push('$'),
+		}
+	}
+
+	expanded.push_str(rest);
+	Some(expanded)
+}
+
+fn split_paths(values: Vec<String>) -> Vec<String> {
+	values
+		.iter()
+		.flat_map(|it| it.split(':'))
+		.filter(|it| !it.is_empty())
+		.map(str::to_owned)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::testing::fixture;
+
+	#[test]
+	fn dynamic_tags_round_trip() {
+		for &it in DynamicTag::all_known() {
+			assert_eq!(DynamicTag::from(u64::from(it)), it);
+		}
+
+		let known = DynamicTag::all_known().iter().map(|&it| u64::from(it));
+		let values = known
+			.flat_map(|it| [it.wrapping_sub(1), it, it.wrapping_add(1)])
+			.chain([
+				0x5FFF_FFFF,
+				0x6000_0000,
+				0x6FFF_FFFF,
+				0x7000_0000,
+				0x7FFF_FFFF,
+				0x8000_0000,
+			])
+			.chain([u64::MAX, u64::MAX / 3]);
+		for value in values {
+			assert_eq!(u64::from(DynamicTag::from(value)), value);
+		}
+	}
+
+	#[test]
+	fn search_paths_expand_tokens() {
+		let mut elf = fixture("hardened");
+		let origin = Path::new("/usr/local/bin");
+		assert_eq!(
+			elf.search_paths(origin).unwrap(),
+			[
+				PathBuf::from("/usr/local/bin/lib"),
+				PathBuf::from("/opt/lib64")
+			]
+		);
+
+		elf.header.ident.is_64_bit = false;
+		assert_eq!(elf.search_paths(origin).unwrap()[1], PathBuf::from("/opt/lib"));
+	}
+
+	#[test]
+	fn rpath_is_used_without_runpath() {
+		let origin = Path::new("/");
+		assert_eq!(
+			fixture("sample-no-relro").search_paths(origin).unwrap(),
+			[PathBuf::from("/opt/legacy")]
+		);
+		assert!(fixture("sample").search_paths(origin).unwrap().is_empty());
+	}
+}
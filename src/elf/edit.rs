@@ -0,0 +1,687 @@
+use std::cell::OnceCell;
+
+use super::{Elf, Result, Section, SectionFlags, SectionType};
+use crate::{
+	consts::{SHN_LORESERVE, SHN_UNDEF, SHN_XINDEX},
+	error::{Error, ErrorKind},
+};
+
+const SYMBOL_ENTRY_SIZE: usize = 24;
+const SYMBOL_SECTION_INDEX_OFFSET: usize = 6;
+const SECTION_HEADER_ALIGNMENT: u64 = 8;
+
+#[derive(Default)]
+pub struct StripOptions {
+	// `.debug_*` sections
+	pub debug: bool,
+	// `.symtab` and its string table
+	pub symbols: bool,
+	// everything else that isn't loaded at runtime, except the section name table
+	pub non_allocated: bool,
+}
+
+impl Elf {
+	pub fn replace_section_data(&mut self, name: &str, data: Vec<u8>) -> Result<()> {
+		let mut index = None;
+		for (position, section) in self.sections.iter().enumerate() {
+			if self.section_name(section)? == name {
+				index = Some(position);
+				break;
+			}
+		}
+		let index = index.ok_or_else(|| format!("No section named {name}"))?;
+
+		self.replace_section_data_at(index, data)
+	}
+
+	fn replace_section_data_at(&mut self, index: usize, data: Vec<u8>) -> Result<()> {
+		let section = &self.sections[index];
+		let name = self.section_name(section)?.to_owned();
+		if section.kind == SectionType::NoBits {
+			return Err(format!("Section {name} has no file data to replace").into());
+		}
+
+		let start = section.offset;
+		let old_end = start + section.size;
+		let new_end = start + data.len() as u64;
+
+		// sections outside the loaded data can just be moved out of the way
+		let is_fixed = self.fixed_sections()[index];
+		let mut needs_relayout = false;
+
+		if new_end > old_end {
+			let overlaps = |offset: u64, size: u64| offset < new_end && offset + size > old_end;
+
+			let header = &self.header;
+			let runs_into_headers = overlaps(
+				header.program_header_offset,
+				(self.segments.len() * header.program_header_stride()) as u64,
+			) || overlaps(
+				header.section_header_offset,
+				(self.sections.len() * header.section_header_stride()) as u64,
+			);
+			let runs_into_section = self.sections.iter().enumerate().any(|(position, it)| {
+				position != index && it.kind != SectionType::NoBits && overlaps(it.offset, it.size)
+			});
+
+			if (runs_into_headers || runs_into_section) && is_fixed {
+				return Err(Error::malformed(format!(
+					"Growing section {name} to {} bytes requires relayout",
+					data.len()
+				)));
+			}
+			needs_relayout = runs_into_headers || runs_into_section;
+		}
+
+		// segments hold their own copy of the bytes, so keep them in sync. they're loaded first,
+		// otherwise they'd still be read from the old file data later
+		let containing: Vec<_> = (0..self.segments.len())
+			.filter(|&it| {
+				let range = self.segments[it].file_range();
+				range.start <= start && old_end <= range.end
+			})
+			.collect();
+		for index in containing {
+			self.load_segment(index)?;
+		}
+
+		for segment in self.segments.iter_mut() {
+			let segment_end = segment.offset + segment.file_size;
+			if start < segment.offset || old_end > segment_end {
+				continue;
+			}
+
+			if new_end > segment_end {
+				let file_size = new_end - segment.offset;
+				if file_size > segment.memory_size {
+					return Err(Error::malformed(format!(
+						"Growing section {name} past its segment's memory size requires relayout"
+					)));
+				}
+
+				segment.file_size = file_size;
+			}
+
+			if let Some(segment_data) = segment.data.get_mut() {
+				let position = (start - segment.offset) as usize;
+				segment_data.resize(segment.file_size as usize, 0);
+				segment_data[position..position + data.len()].copy_from_slice(&data);
+			}
+		}
+
+		let section = &mut self.sections[index];
+		section.size = data.len() as u64;
+		section.data = OnceCell::from(data);
+		self.symbols.take();
+
+		if needs_relayout {
+			self.relayout()?;
+		}
+
+		Ok(())
+	}
+
+	// allocated sections keep their place in the loaded data, everything else is laid out again
+	// after it, followed by the section header table. the name table is rebuilt too, which drops
+	// names no section uses anymore
+	pub fn rebuild_layout(&mut self) -> Result<()> {
+		if self.section_name_table().is_some() && !self.section_names_are_shared() {
+			let mut section_names = Vec::with_capacity(self.sections.len());
+			for section in &self.sections {
+				section_names.push(self.section_name(section)?.to_owned());
+			}
+			let section_names: Vec<_> = section_names.iter().map(String::as_str).collect();
+
+			let (names, name_indices) = build_name_table(&section_names);
+			let names_index = self.section_names_index();
+			let table = &mut self.sections[names_index];
+			table.size = names.len() as u64;
+			table.data = OnceCell::from(names);
+			for (section, name_index) in self.sections.iter_mut().zip(name_indices) {
+				section.name_index = name_index;
+			}
+		}
+
+		self.relayout()
+	}
+
+	// some linkers put the symbol names in the same table, which then can't be rebuilt from the
+	// section names alone
+	fn section_names_are_shared(&self) -> bool {
+		let names_index = self.section_names_index() as u32;
+		self.sections.iter().any(|it| {
+			matches!(it.kind, SectionType::SymbolTable | SectionType::LinkerSymbolTable)
+				&& it.link == names_index
+		})
+	}
+
+	fn relayout(&mut self) -> Result<()> {
+		// moved sections are written from their bodies
+		self.load_sections()?;
+
+		self.check_loaded_layout()?;
+		let fixed = self.fixed_sections();
+		self.pack_sections(&fixed);
+
+		Ok(())
+	}
+
+	// the loader maps whole pages, so a segment's offset and address have to agree modulo its
+	// alignment, and sections inside it have to be at the same distance from its start in both
+	fn check_loaded_layout(&self) -> Result<()> {
+		for (index, segment) in self.segments.iter().enumerate().filter(|(_, it)| it.is_loadable())
+		{
+			let alignment = segment.alignment.max(1);
+			if segment.offset % alignment != segment.virtual_address % alignment {
+				return Err(Error::malformed(format!(
+					"Segment {index} offset {:#x} and address {:#x} are not congruent modulo {alignment:#x}",
+					segment.offset, segment.virtual_address
+				)));
+			}
+			if segment.file_size > segment.memory_size {
+				return Err(Error::malformed(format!(
+					"Segment {index} has more file data than it maps"
+				)));
+			}
+		}
+
+		for (index, section) in self.sections.iter().enumerate() {
+			if !section.is_allocated() || section.kind == SectionType::NoBits || section.size == 0 {
+				continue;
+			}
+			let Some(segment) = self.loadable_segments().find(|it| {
+				section.address >= it.virtual_address
+					&& section.address < it.virtual_address + it.memory_size
+			}) else {
+				continue;
+			};
+
+			let distance = section.address - segment.virtual_address;
+			if segment.offset + distance != section.offset
+				|| distance + section.size > segment.file_size
+			{
+				return Err(Error::malformed(format!(
+					"Section {index} doesn't match the file data of the segment mapping it"
+				)));
+			}
+		}
+
+		Ok(())
+	}
+
+	// allocated sections in a loadable segment, which can't move without moving the segment.
+	// files without segments (i.e. objects) have none
+	fn fixed_sections(&self) -> Vec<bool> {
+		self.sections
+			.iter()
+			.map(|section| {
+				section.is_allocated()
+					&& self
+						.loadable_segments()
+						.any(|it| it.memory_range().contains(&section.address))
+			})
+			.collect()
+	}
+
+	// everything loaded stays where it is, the rest is packed right after it
+	fn pack_sections(&mut self, fixed: &[bool]) {
+		let header = &self.header;
+		let mut end = self
+			.segments
+			.iter()
+			.map(|it| it.file_range().end)
+			.chain(
+				self.sections
+					.iter()
+					.zip(fixed)
+					.filter(|(_, &it)| it)
+					.map(|(it, _)| it.file_range().end),
+			)
+			.chain([
+				header.header_size as u64,
+				header.program_header_offset
+					+ (self.segments.len() * header.program_header_stride()) as u64,
+			])
+			.max()
+			.unwrap_or_default();
+
+		for (section, _) in self.sections.iter_mut().zip(fixed).skip(1).filter(|(_, &it)| !it) {
+			section.offset = end.next_multiple_of(section.address_alignment.max(1));
+			end = section.file_range().end;
+		}
+
+		// without sections there's no table to point at
+		self.header.section_header_offset = if self.sections.is_empty() {
+			0
+		} else {
+			end.next_multiple_of(SECTION_HEADER_ALIGNMENT)
+		};
+
+		// with too many sections for `e_shnum` it's left 0, and the count goes in section 0 instead
+		let count = self.sections.len();
+		let header_count = u16::try_from(count).ok().filter(|&it| it < SHN_LORESERVE);
+		self.header.section_header_count = header_count.unwrap_or(0);
+		if let Some(first) = self.sections.first_mut() {
+			first.size = if header_count.is_some() {
+				0
+			} else {
+				count as u64
+			};
+		}
+	}
+
+	// like the count, an index too large for `e_shstrndx` is moved to `sh_link` of section 0
+	fn set_section_names_index(&mut self, index: usize) {
+		let header_index = u16::try_from(index).ok().filter(|&it| it < SHN_LORESERVE);
+		self.header.section_header_names_index = header_index.unwrap_or(SHN_XINDEX);
+		if let Some(first) = self.sections.first_mut() {
+			first.link = if header_index.is_some() {
+				0
+			} else {
+				index as u32
+			};
+		}
+	}
+
+	// the name table is rebuilt like `strip` does, which fails if it has to grow into other data
+	pub fn rename_section(&mut self, old: &str, new: &str) -> Result<()> {
+		if self.section_name_table().is_none() {
+			return Err(Error::new(ErrorKind::Unsupported, "No valid section name string table"));
+		}
+		if self.section_names_are_shared() {
+			return Err("Section name table also holds symbol names".into());
+		}
+		let names_index = self.section_names_index();
+
+		let mut renamed = None;
+		let mut section_names = Vec::with_capacity(self.sections.len());
+		for (index, section) in self.sections.iter().enumerate() {
+			let name = self.section_name(section)?;
+			if renamed.is_none() && index != 0 && name == old {
+				renamed = Some(index);
+				section_names.push(new);
+			} else {
+				section_names.push(name);
+			}
+		}
+		if renamed.is_none() {
+			return Err(format!("No section named {old}").into());
+		}
+
+		let (names, name_indices) = build_name_table(&section_names);
+		self.replace_section_data_at(names_index, names)?;
+		for (section, name_index) in self.sections.iter_mut().zip(name_indices) {
+			section.name_index = name_index;
+		}
+
+		Ok(())
+	}
+
+	// drops sections and packs the remaining non-allocated ones after the loaded data, segments and
+	// their data are left alone
+	pub fn strip(&mut self, options: StripOptions) -> Result<()> {
+		// the kept sections get moved around, which needs their bodies
+		self.load_sections()?;
+
+		let names_index = self.section_names_index();
+
+		let mut removed = vec![false; self.sections.len()];
+		for (index, section) in self.sections.iter().enumerate() {
+			if index == 0 || index == names_index || section.is_allocated() {
+				continue;
+			}
+
+			let name = self.section_name(section)?;
+			removed[index] = options.non_allocated
+				|| options.debug && (name.starts_with(".debug_") || name.starts_with(".zdebug_"))
+				|| options.symbols && section.kind == SectionType::SymbolTable;
+		}
+
+		// string tables go with the symbol tables using them and relocations with their targets,
+		// but anything still linked from a kept section has to stay
+		for (index, section) in self.sections.iter().enumerate() {
+			if !removed[index] || section.kind != SectionType::SymbolTable {
+				continue;
+			}
+			if let Some(link) = removed.get_mut(section.link as usize) {
+				*link |= section.link as usize != names_index;
+			}
+		}
+		for (index, section) in self.sections.iter().enumerate() {
+			let has_info_link = section.section_flags().contains(SectionFlags::INFO_LINK);
+			if has_info_link
+				&& !section.is_allocated()
+				&& removed.get(section.info as usize) == Some(&true)
+			{
+				removed[index] = true;
+			}
+		}
+		let mut changed = true;
+		while changed {
+			changed = false;
+			for (index, section) in self.sections.iter().enumerate() {
+				let link = section.link as usize;
+				if !removed[index] && removed.get(link) == Some(&true) {
+					removed[link] = false;
+					changed = true;
+				}
+			}
+		}
+
+		// allocated symbol tables (i.e. `.dynsym`) are part of the segments, so the section indices
+		// in them can't change
+		let has_allocated_symbols = self.sections.iter().any(|it| {
+			it.is_allocated()
+				&& matches!(it.kind, SectionType::SymbolTable | SectionType::LinkerSymbolTable)
+		});
+		let first_removed = removed.iter().position(|&it| it);
+		let last_allocated = self.sections.iter().rposition(Section::is_allocated);
+		if let (true, Some(first_removed), Some(last_allocated)) =
+			(has_allocated_symbols, first_removed, last_allocated)
+		{
+			if first_removed < last_allocated {
+				return Err(Error::malformed(format!(
+					"Stripping section {first_removed} would renumber allocated section {last_allocated}"
+				)));
+			}
+		}
+
+		let mut new_indices = Vec::with_capacity(removed.len());
+		let mut count = 0;
+		for &is_removed in &removed {
+			new_indices.push((!is_removed).then_some(count));
+			count += usize::from(!is_removed);
+		}
+		let remap =
+			|index: u32| new_indices.get(index as usize).copied().flatten().unwrap_or(0) as u32;
+
+		// the name table is rebuilt from scratch, so names of dropped sections don't linger
+		let mut section_names = Vec::with_capacity(count);
+		for (section, _) in self.sections.iter().zip(&removed).filter(|(_, &it)| !it) {
+			section_names.push(self.section_name(section)?);
+		}
+		let (mut names, name_indices) = build_name_table(&section_names);
+
+		let is_little_endian = self.header.ident.is_little_endian;
+		let old_sections = std::mem::take(&mut self.sections);
+		for (index, mut section) in old_sections.into_iter().enumerate() {
+			let Some(new_index) = new_indices[index] else {
+				continue;
+			};
+
+			section.name_index = name_indices[new_index];
+			section.link = remap(section.link);
+			if section.section_flags().contains(SectionFlags::INFO_LINK) {
+				section.info = remap(section.info);
+			}
+
+			// symbols of dropped sections (e.g. the section symbols of `.debug_*`) end up undefined
+			let is_symbol_table =
+				section.kind == SectionType::SymbolTable && !section.is_allocated();
+			if let Some(data) = section.data.get_mut().filter(|_| is_symbol_table) {
+				for entry in data.chunks_exact_mut(SYMBOL_ENTRY_SIZE) {
+					let field =
+						&mut entry[SYMBOL_SECTION_INDEX_OFFSET..SYMBOL_SECTION_INDEX_OFFSET + 2];
+					let bytes = [field[0], field[1]];
+					let old = if is_little_endian {
+						u16::from_le_bytes(bytes)
+					} else {
+						u16::from_be_bytes(bytes)
+					};
+					if old == SHN_UNDEF || old >= SHN_LORESERVE {
+						continue;
+					}
+
+					// sections are only removed, so the index can only get smaller
+					let Ok(new) = u16::try_from(remap(old.into())) else {
+						continue;
+					};
+					let bytes = if is_little_endian {
+						new.to_le_bytes()
+					} else {
+						new.to_be_bytes()
+					};
+					field.copy_from_slice(&bytes);
+				}
+			}
+
+			if index == names_index {
+				section.size = names.len() as u64;
+				section.data = OnceCell::from(std::mem::take(&mut names));
+			}
+
+			self.sections.push(section);
+		}
+		self.set_section_names_index(remap(names_index as u32) as usize);
+
+		let fixed: Vec<_> = self.sections.iter().map(Section::is_allocated).collect();
+		self.pack_sections(&fixed);
+		self.symbols.take();
+
+		Ok(())
+	}
+}
+
+// names are added longest first so shorter ones can point into the end of a longer one, like the
+// linker does with e.g. `.rela.text` and `.text`
+fn build_name_table(section_names: &[&str]) -> (Vec<u8>, Vec<usize>) {
+	let mut order: Vec<_> = (0..section_names.len()).collect();
+	order.sort_by_key(|&it| std::cmp::Reverse(section_names[it].len()));
+
+	// starting with the empty string, which the null section points at
+	let mut names = vec![0];
+	let mut name_indices = vec![0; section_names.len()];
+
+	for index in order {
+		let mut name = section_names[index].as_bytes().to_vec();
+		name.push(0);
+
+		name_indices[index] = match names.windows(name.len()).position(|it| it == name) {
+			Some(position) => position,
+			None => {
+				names.extend_from_slice(&name);
+				names.len() - name.len()
+			}
+		};
+	}
+
+	(names, name_indices)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		elf::FILE_HEADER_SIZE,
+		testing::{fixture, fixture_bytes, program_header_at, write_u64_at},
+	};
+
+	#[test]
+	fn rebuilt_layout_round_trips() {
+		let original = fixture("sample");
+		let mut elf = fixture("sample");
+
+		// grown into the sections after it, which have to move since they aren't mapped
+		let comment = original.section_by_name(".comment").unwrap().unwrap();
+		let comment = [original.section_data(comment).unwrap(), &[b'!'; 0x100]].concat();
+		elf.replace_section_data(".comment", comment.clone()).unwrap();
+		elf.rebuild_layout().unwrap();
+
+		let mut bytes = Vec::new();
+		elf.write(&mut bytes).unwrap();
+		let rebuilt = Elf::try_from(bytes.as_slice()).unwrap();
+
+		assert_eq!(rebuilt.sections.len(), original.sections.len());
+		for (before, after) in original.sections.iter().zip(&rebuilt.sections) {
+			let name = original.section_name(before).unwrap();
+			assert_eq!(rebuilt.section_name(after).unwrap(), name);
+			assert_eq!(after.kind, before.kind, "{name}");
+			assert_eq!(after.address, before.address, "{name}");
+			if before.is_allocated() {
+				assert_eq!(after.offset, before.offset, "{name}");
+			}
+
+			let data = rebuilt.section_data(after).unwrap();
+			match name {
+				".comment" => assert_eq!(data, comment),
+				// the names may be laid out in a different order
+				".shstrtab" => {}
+				_ => assert_eq!(data, original.section_data(before).unwrap(), "{name}"),
+			}
+		}
+
+		let symbols =
+			|elf: &Elf| elf.symbols().unwrap().into_iter().map(|it| it.name).collect::<Vec<_>>();
+		assert_eq!(symbols(&rebuilt), symbols(&original));
+	}
+
+	#[test]
+	fn rebuilding_the_layout_keeps_segments_mappable() {
+		let mut bytes = fixture_bytes("sample");
+		// `p_offset` of the text segment, which the loader couldn't map anymore
+		let text = program_header_at(&bytes, 3);
+		write_u64_at(&mut bytes, text + 0x8, 0x1008);
+		let mut elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		let error = elf.rebuild_layout().unwrap_err();
+		assert_eq!(error.kind(), &ErrorKind::Malformed);
+		assert_eq!(
+			error.to_string(),
+			"Segment 3 offset 0x1008 and address 0x1000 are not congruent modulo 0x1000"
+		);
+	}
+
+	#[test]
+	fn stripped_files_keep_everything_else() {
+		let original = fixture("sample-debug");
+		let mut elf = fixture("sample-debug");
+		elf.strip(StripOptions {
+			debug: true,
+			symbols: true,
+			..StripOptions::default()
+		})
+		.unwrap();
+
+		let mut bytes = Vec::new();
+		elf.write(&mut bytes).unwrap();
+		assert!(bytes.len() < fixture_bytes("sample-debug").len());
+		let stripped = Elf::try_from(bytes.as_slice()).unwrap();
+
+		// also checks every name still resolves in the rebuilt `.shstrtab`
+		let names = |elf: &Elf| {
+			elf.sections
+				.iter()
+				.map(|it| elf.section_name(it).unwrap().to_owned())
+				.collect::<Vec<_>>()
+		};
+		let kept: Vec<_> = names(&original)
+			.into_iter()
+			.filter(|it| !it.starts_with(".debug_") && it != ".symtab" && it != ".strtab")
+			.collect();
+		assert_eq!(names(&stripped), kept);
+		assert!(stripped.static_symbols().unwrap().is_empty());
+
+		for name in kept.iter().filter(|it| *it != ".shstrtab") {
+			let before = original.section_by_name(name).unwrap().unwrap();
+			let after = stripped.section_by_name(name).unwrap().unwrap();
+			assert_eq!(after.address, before.address, "{name}");
+			assert_eq!(
+				stripped.section_data(after).unwrap(),
+				original.section_data(before).unwrap(),
+				"{name}"
+			);
+		}
+		assert_eq!(stripped.segments.len(), original.segments.len());
+		for (before, after) in original.segments.iter().zip(&stripped.segments) {
+			assert_eq!(after.file_range(), before.file_range());
+			assert_eq!(after.virtual_address, before.virtual_address);
+			// apart from the section header fields in the file header
+			let skipped = if before.file_range().contains(&0) {
+				FILE_HEADER_SIZE
+			} else {
+				0
+			};
+			let data = |elf: &Elf, segment| elf.segment_data(segment).unwrap()[skipped..].to_vec();
+			assert!(data(&stripped, after) == data(&original, before));
+		}
+
+		let dynamic = |elf: &Elf| {
+			elf.dynamic_symbols().unwrap().into_iter().map(|it| it.name).collect::<Vec<_>>()
+		};
+		assert_eq!(dynamic(&stripped), dynamic(&original));
+	}
+
+	#[test]
+	fn renamed_sections_are_found_by_their_new_name() {
+		let original = fixture("sample");
+		let mut elf = fixture("sample");
+		let new = ".comment.renamed.to.something.longer";
+		elf.rename_section(".comment", new).unwrap();
+
+		let mut bytes = Vec::new();
+		elf.write(&mut bytes).unwrap();
+		let renamed = Elf::try_from(bytes.as_slice()).unwrap();
+
+		// the longer name doesn't fit the old table
+		let names_size = |elf: &Elf| elf.section_name_table().unwrap().size;
+		assert!(names_size(&renamed) > names_size(&original));
+
+		assert!(renamed.section_by_name(".comment").unwrap().is_none());
+		let comment = renamed.section_by_name(new).unwrap().unwrap();
+		let before = original.section_by_name(".comment").unwrap().unwrap();
+		assert_eq!(renamed.section_data(comment).unwrap(), original.section_data(before).unwrap());
+
+		for (before, after) in original.sections.iter().zip(&renamed.sections) {
+			let name = original.section_name(before).unwrap();
+			if name != ".comment" {
+				assert_eq!(renamed.section_name(after).unwrap(), name);
+			}
+		}
+
+		let error = elf.rename_section(".comment", ".other").unwrap_err();
+		assert_eq!(error.to_string(), "No section named .comment");
+	}
+
+	#[test]
+	fn large_section_counts_go_in_section_zero() {
+		let mut elf = fixture("sample");
+		elf.load_sections().unwrap();
+		let original_count = elf.sections.len();
+
+		// empty sections in front of the name table push its index past `SHN_LORESERVE` too
+		let names_index = elf.section_names_index();
+		let fillers = (0..SHN_LORESERVE).map(|_| Section {
+			name_index: 0,
+			kind: SectionType::ProgramData,
+			flags: 0,
+			address: 0,
+			offset: 0,
+			size: 0,
+			link: 0,
+			info: 0,
+			address_alignment: 1,
+			entry_size: 0,
+			data: OnceCell::from(Vec::new()),
+		});
+		elf.sections.splice(names_index..names_index, fillers);
+		elf.set_section_names_index(names_index + SHN_LORESERVE as usize);
+		elf.strip(StripOptions {
+			symbols: true,
+			..StripOptions::default()
+		})
+		.unwrap();
+
+		let mut bytes = Vec::new();
+		elf.write(&mut bytes).unwrap();
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		let count = original_count - 2 + SHN_LORESERVE as usize;
+		assert_eq!(elf.header.section_header_count, 0);
+		assert_eq!(elf.header.section_header_names_index, SHN_XINDEX);
+		assert_eq!(elf.sections().len(), count);
+		assert_eq!(elf.sections()[0].size, count as u64);
+		assert_eq!(elf.section_names_index(), count - 1);
+		assert!(elf.section_by_name(".text").unwrap().is_some());
+		assert!(elf.section_by_name(".symtab").unwrap().is_none());
+	}
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use super::{Elf, Result};
+use crate::{
+	consts::{
+		DW_EH_PE_ABSPTR, DW_EH_PE_INDIRECT, DW_EH_PE_OMIT, DW_EH_PE_PCREL, DW_EH_PE_SDATA2,
+		DW_EH_PE_SDATA4, DW_EH_PE_SDATA8, DW_EH_PE_SLEB128, DW_EH_PE_UDATA2, DW_EH_PE_UDATA4,
+		DW_EH_PE_UDATA8, DW_EH_PE_ULEB128,
+	},
+	error::Error,
+	reader::Reader,
+};
+
+const EXTENDED_LENGTH: u32 = 0xFFFF_FFFF;
+const CIE_ID: u32 = 0;
+
+#[derive(Debug, PartialEq)]
+pub struct FunctionRange {
+	pub start: u64,
+	pub end: u64,
+}
+
+impl Elf {
+	// every FDE covers one function, which survives stripping since unwinding needs it
+	pub fn functions_from_eh_frame(&self) -> Result<Vec<FunctionRange>> {
+		let Some(section) = self.section_by_name(".eh_frame")? else {
+			return Ok(Vec::new());
+		};
+
+		let mut functions = Vec::new();
+		// FDE pointer encodings by the offset of their CIE
+		let mut encodings = HashMap::new();
+		let data = self.section_data(section)?;
+		let mut reader = self.reader_for(data);
+
+		while !reader.is_empty() {
+			let start = reader.position();
+			let mut length = reader.read_u32()? as u64;
+			if length == 0 {
+				break;
+			}
+			if length == EXTENDED_LENGTH as u64 {
+				length = reader.read_u64()?;
+			}
+
+			let id_position = reader.position();
+			let end = usize::try_from(length)
+				.ok()
+				.and_then(|it| id_position.checked_add(it))
+				.filter(|&it| it <= data.len())
+				.ok_or_else(|| {
+					Error::malformed(format!("Frame record at {start:#x} runs past the section"))
+				})?;
+
+			let id = reader.read_u32()?;
+			if id == CIE_ID {
+				encodings.insert(start, read_cie(&mut reader, section.address)?);
+			} else {
+				// the CIE pointer counts backwards from its own position
+				let cie = id_position.checked_sub(id as usize).ok_or_else(|| {
+					Error::malformed(format!("FDE at {start:#x} points before the section"))
+				})?;
+				let encoding = *encodings.get(&cie).ok_or_else(|| {
+					Error::malformed(format!("FDE at {start:#x} has no CIE at {cie:#x}"))
+				})?;
+
+				let start = read_pointer(&mut reader, encoding, section.address)?;
+				// the range is a plain length, so only the value format applies
+				let size = read_pointer(&mut reader, encoding & 0x0F, section.address)?;
+				functions.push(FunctionRange {
+					start,
+					end: start.wrapping_add(size),
+				});
+			}
+
+			reader.seek(end)?;
+		}
+
+		Ok(functions)
+	}
+}
+
+// returns the encoding of the pointers in the FDEs referring to this CIE
+fn read_cie(reader: &mut Reader, base: u64) -> Result<u8> {
+	let version = reader.read_u8()?;
+
+	let mut augmentation = Vec::new();
+	loop {
+		match reader.read_u8()? {
+			0 => break,
+			it => augmentation.push(it),
+		}
+	}
+
+	// pre-`z` GCC stored a pointer to the exception table right here
+	if augmentation.starts_with(b"eh") {
+		reader.read_u64()?;
+	}
+
+	let _code_alignment = reader.read_uleb128()?;
+	let _data_alignment = reader.read_sleb128()?;
+	if version == 1 {
+		reader.read_u8()?;
+	} else {
+		reader.read_uleb128()?;
+	}
+
+	let mut encoding = DW_EH_PE_ABSPTR;
+	if augmentation.first() != Some(&b'z') {
+		return Ok(encoding);
+	}
+
+	let _augmentation_length = reader.read_uleb128()?;
+	for character in &augmentation[1..] {
+		match character {
+			b'R' => encoding = reader.read_u8()?,
+			b'P' => {
+				// the personality routine is only skipped, so there's nothing to dereference
+				let personality = reader.read_u8()?;
+				read_pointer(reader, personality & !DW_EH_PE_INDIRECT, base)?;
+			}
+			b'L' => {
+				reader.read_u8()?;
+			}
+			b'S' | b'B' => {}
+			// the remaining data can't be interpreted without knowing the character
+			_ => break,
+		}
+	}
+
+	Ok(encoding)
+}
+
+// `base` is the address the data is read from, which pc-relative values are relative to
+fn read_pointer(reader: &mut Reader, encoding: u8, base: u64) -> Result<u64> {
+	if encoding == DW_EH_PE_OMIT {
+		return Ok(0);
+	}
+	if encoding & DW_EH_PE_INDIRECT != 0 {
+		return Err(format!("Indirect pointer encoding {encoding:#x} is not supported").into());
+	}
+
+	let address = base + reader.position() as u64;
+	let value = match encoding & 0x0F {
+		DW_EH_PE_ABSPTR | DW_EH_PE_UDATA8 | DW_EH_PE_SDATA8 => reader.read_u64()?,
+		DW_EH_PE_ULEB128 => reader.read_uleb128()?,
+		DW_EH_PE_UDATA2 => reader.read_u16()? as u64,
+		DW_EH_PE_UDATA4 => reader.read_u32()? as u64,
+		DW_EH_PE_SLEB128 => reader.read_sleb128()? as u64,
+		DW_EH_PE_SDATA2 => reader.read_u16()? as i16 as u64,
+		DW_EH_PE_SDATA4 => reader.read_u32()? as i32 as u64,
+		format => return Err(format!("Unknown pointer format {format:#x}").into()),
+	};
+
+	match encoding & 0x70 {
+		0 => Ok(value),
+		DW_EH_PE_PCREL => Ok(address.wrapping_add(value)),
+		application => Err(format!("Pointer application {application:#x} is not supported").into()),
+	}
+}
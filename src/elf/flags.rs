@@ -0,0 +1,198 @@
+use std::ops::BitOr;
+
+use super::{Elf, FileHeader, Machine, Section};
+use crate::consts::{
+	SHF_ALLOC, SHF_COMPRESSED, SHF_EXECINSTR, SHF_GROUP, SHF_INFO_LINK, SHF_LINK_ORDER, SHF_MERGE,
+	SHF_OS_NONCONFORMING, SHF_STRINGS, SHF_TLS, SHF_WRITE,
+};
+
+const EF_ARM_EABI_MASK: u32 = 0xFF00_0000;
+const EF_ARM_BE8: u32 = 0x0080_0000;
+const EF_ARM_ABI_FLOAT_HARD: u32 = 0x0000_0400;
+const EF_ARM_ABI_FLOAT_SOFT: u32 = 0x0000_0200;
+
+const EF_MIPS_NOREORDER: u32 = 0x0000_0001;
+const EF_MIPS_PIC: u32 = 0x0000_0002;
+const EF_MIPS_CPIC: u32 = 0x0000_0004;
+const EF_MIPS_ABI2: u32 = 0x0000_0020;
+const EF_MIPS_FP64: u32 = 0x0000_0200;
+const EF_MIPS_NAN2008: u32 = 0x0000_0400;
+const EF_MIPS_ABI: u32 = 0x0000_F000;
+const EF_MIPS_ARCH: u32 = 0xF000_0000;
+
+#[derive(Debug, PartialEq)]
+pub enum HeaderFlags {
+	Arm(ArmFlags),
+	Mips(MipsFlags),
+	Other(u32),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ArmFlags {
+	pub eabi_version: u8,
+	pub be8: bool,
+	pub hard_float: bool,
+	pub soft_float: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MipsFlags {
+	pub noreorder: bool,
+	pub pic: bool,
+	pub cpic: bool,
+	pub abi2: bool,
+	pub fp64: bool,
+	pub nan2008: bool,
+	pub abi: MipsAbi,
+	pub arch: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MipsAbi {
+	None,
+	O32,
+	O64,
+	Eabi32,
+	Eabi64,
+	Other(u32),
+}
+
+impl From<u32> for MipsAbi {
+	fn from(value: u32) -> Self {
+		match value {
+			0x0000 => Self::None,
+			0x1000 => Self::O32,
+			0x2000 => Self::O64,
+			0x3000 => Self::Eabi32,
+			0x4000 => Self::Eabi64,
+			_ => Self::Other(value),
+		}
+	}
+}
+
+impl From<MipsAbi> for u32 {
+	fn from(abi: MipsAbi) -> Self {
+		match abi {
+			MipsAbi::None => 0x0000,
+			MipsAbi::O32 => 0x1000,
+			MipsAbi::O64 => 0x2000,
+			MipsAbi::Eabi32 => 0x3000,
+			MipsAbi::Eabi64 => 0x4000,
+			MipsAbi::Other(value) => value,
+		}
+	}
+}
+
+impl FileHeader {
+	pub fn decoded_flags(&self) -> HeaderFlags {
+		let flags = self.flags;
+
+		match self.machine {
+			Machine::Arm => HeaderFlags::Arm(ArmFlags {
+				eabi_version: ((flags & EF_ARM_EABI_MASK) >> 24) as u8,
+				be8: flags & EF_ARM_BE8 != 0,
+				hard_float: flags & EF_ARM_ABI_FLOAT_HARD != 0,
+				soft_float: flags & EF_ARM_ABI_FLOAT_SOFT != 0,
+			}),
+			Machine::Mips => HeaderFlags::Mips(MipsFlags {
+				noreorder: flags & EF_MIPS_NOREORDER != 0,
+				pic: flags & EF_MIPS_PIC != 0,
+				cpic: flags & EF_MIPS_CPIC != 0,
+				abi2: flags & EF_MIPS_ABI2 != 0,
+				fp64: flags & EF_MIPS_FP64 != 0,
+				nan2008: flags & EF_MIPS_NAN2008 != 0,
+				abi: MipsAbi::from(flags & EF_MIPS_ABI),
+				arch: ((flags & EF_MIPS_ARCH) >> 28) as u8,
+			}),
+			_ => HeaderFlags::Other(flags),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SectionFlags(pub u64);
+
+impl SectionFlags {
+	pub const WRITE: Self = Self(SHF_WRITE);
+	pub const ALLOC: Self = Self(SHF_ALLOC);
+	pub const EXECUTE: Self = Self(SHF_EXECINSTR);
+	pub const MERGE: Self = Self(SHF_MERGE);
+	pub const STRINGS: Self = Self(SHF_STRINGS);
+	pub const INFO_LINK: Self = Self(SHF_INFO_LINK);
+	pub const LINK_ORDER: Self = Self(SHF_LINK_ORDER);
+	pub const OS_NONCONFORMING: Self = Self(SHF_OS_NONCONFORMING);
+	pub const GROUP: Self = Self(SHF_GROUP);
+	pub const TLS: Self = Self(SHF_TLS);
+	pub const COMPRESSED: Self = Self(SHF_COMPRESSED);
+
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl BitOr for SectionFlags {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl From<u64> for SectionFlags {
+	fn from(value: u64) -> Self {
+		Self(value)
+	}
+}
+
+impl From<SectionFlags> for u64 {
+	fn from(flags: SectionFlags) -> Self {
+		flags.0
+	}
+}
+
+impl Section {
+	pub fn section_flags(&self) -> SectionFlags {
+		SectionFlags(self.flags)
+	}
+
+	pub fn is_allocated(&self) -> bool {
+		self.section_flags().contains(SectionFlags::ALLOC)
+	}
+}
+
+impl Elf {
+	pub fn sections_with_flags(&self, required: SectionFlags) -> impl Iterator<Item = &Section> {
+		self.sections.iter().filter(move |it| it.section_flags().contains(required))
+	}
+
+	// sections that are both writable and executable are a red flag for exploitability
+	pub fn writable_executable_sections(&self) -> impl Iterator<Item = &Section> {
+		self.sections_with_flags(SectionFlags::WRITE | SectionFlags::EXECUTE)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::testing::fixture;
+
+	#[test]
+	fn flags_are_decoded_for_the_file_machine() {
+		assert_eq!(fixture("sample").header.decoded_flags(), HeaderFlags::Other(0));
+
+		// `readelf -h` shows noreorder, cpic, mips64
+		assert_eq!(
+			fixture("mips64-be.o").header.decoded_flags(),
+			HeaderFlags::Mips(MipsFlags {
+				noreorder: true,
+				pic: false,
+				cpic: true,
+				abi2: false,
+				fp64: false,
+				nan2008: false,
+				abi: MipsAbi::None,
+				arch: 6,
+			})
+		);
+	}
+}
@@ -0,0 +1,166 @@
+use super::{Elf, Result};
+use crate::{error::Error, reader::Reader};
+
+const NT_GO_BUILD_ID: u32 = 4;
+
+const BUILD_INFO_MAGIC: &[u8] = b"\xFF Go buildinf:";
+const BUILD_INFO_HEADER_SIZE: usize = 32;
+const BUILD_INFO_FLAG_BIG_ENDIAN: u8 = 0x1;
+// since Go 1.18 the strings follow the header instead of being pointed to
+const BUILD_INFO_FLAG_INLINE: u8 = 0x2;
+// module info is wrapped in 16-byte sentinels so it can be found in the binary
+const MODULE_INFO_SENTINEL_SIZE: usize = 16;
+
+#[derive(Debug, PartialEq)]
+pub struct GoBuildInfo {
+	pub version: String,
+	pub module_info: String,
+}
+
+impl Elf {
+	pub fn go_build_id(&self) -> Result<Option<String>> {
+		let notes = self.notes()?;
+		let Some(note) = notes.iter().find(|it| it.name == "Go" && it.kind == NT_GO_BUILD_ID)
+		else {
+			return Ok(None);
+		};
+
+		Ok(Some(String::from_utf8(note.description.clone())?))
+	}
+
+	pub fn go_build_info(&self) -> Result<Option<GoBuildInfo>> {
+		let Some(section) = self.section_by_name(".go.buildinfo")? else {
+			return Ok(None);
+		};
+
+		let data = self.section_data(section)?;
+		if data.len() < BUILD_INFO_HEADER_SIZE || !data.starts_with(BUILD_INFO_MAGIC) {
+			return Err(Error::malformed("Go build info has no valid header"));
+		}
+
+		let pointer_size = data[BUILD_INFO_MAGIC.len()] as usize;
+		let flags = data[BUILD_INFO_MAGIC.len() + 1];
+
+		let (version, module_info) = if flags & BUILD_INFO_FLAG_INLINE != 0 {
+			let mut reader = self.reader_for(data);
+			reader.seek(BUILD_INFO_HEADER_SIZE)?;
+			(read_inline_bytes(&mut reader)?, read_inline_bytes(&mut reader)?)
+		} else {
+			if pointer_size != 4 && pointer_size != 8 {
+				return Err(Error::malformed(format!(
+					"Go build info has invalid pointer size {pointer_size}"
+				)));
+			}
+
+			// the header has its own byte order flag, independent of the file's
+			let is_little_endian = flags & BUILD_INFO_FLAG_BIG_ENDIAN == 0;
+			let mut reader = Reader::new(data, is_little_endian);
+			reader.seek(BUILD_INFO_MAGIC.len() + 2)?;
+			let version = read_pointer(&mut reader, pointer_size)?;
+			let module_info = read_pointer(&mut reader, pointer_size)?;
+
+			(
+				self.read_go_string(version, pointer_size, is_little_endian)?,
+				self.read_go_string(module_info, pointer_size, is_little_endian)?,
+			)
+		};
+
+		Ok(Some(GoBuildInfo {
+			version: String::from_utf8(version)?,
+			module_info: String::from_utf8(strip_sentinels(&module_info).to_vec())?,
+		}))
+	}
+
+	// a Go string is a pointer to its bytes followed by its length
+	fn read_go_string(
+		&self,
+		address: u64,
+		pointer_size: usize,
+		is_little_endian: bool,
+	) -> Result<Vec<u8>> {
+		let header = self.read_virtual(address, 2 * pointer_size)?;
+		let mut reader = Reader::new(&header, is_little_endian);
+		let data = read_pointer(&mut reader, pointer_size)?;
+		let size = read_pointer(&mut reader, pointer_size)?;
+
+		// the length comes from the file, so it's only trusted as far as the segment's file data goes
+		let available = self
+			.loadable_segments()
+			.find(|it| data >= it.virtual_address && data - it.virtual_address < it.file_size)
+			.map_or(0, |it| it.file_size - (data - it.virtual_address));
+		if size > available {
+			return Err(Error::malformed(format!(
+				"Go string at {address:#x} is longer than the segment holding it"
+			)));
+		}
+
+		self.read_virtual(data, size as usize)
+	}
+}
+
+fn read_pointer(reader: &mut Reader, size: usize) -> Result<u64> {
+	if size == 4 {
+		reader.read_u32().map(u64::from)
+	} else {
+		reader.read_u64()
+	}
+}
+
+fn read_inline_bytes(reader: &mut Reader) -> Result<Vec<u8>> {
+	let size = reader.read_uleb128()? as usize;
+	Ok(reader.read_bytes(size)?.to_vec())
+}
+
+// the sentinels aren't valid UTF-8, so they have to go before the text is decoded
+fn strip_sentinels(module_info: &[u8]) -> &[u8] {
+	let size = module_info.len();
+	if size <= 2 * MODULE_INFO_SENTINEL_SIZE
+		|| module_info[size - MODULE_INFO_SENTINEL_SIZE - 1] != b'\n'
+	{
+		return module_info;
+	}
+
+	&module_info[MODULE_INFO_SENTINEL_SIZE..size - MODULE_INFO_SENTINEL_SIZE]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		error::ErrorKind,
+		testing::{fixture, fixture_bytes, read_u64_at, write_u64_at},
+	};
+
+	#[test]
+	fn go_build_id_is_read_from_a_padded_note() {
+		let elf = fixture("go-buildid.o");
+		assert_eq!(elf.go_build_id().unwrap().as_deref(), Some("abc/def/ghij"));
+
+		assert_eq!(fixture("sample").go_build_id().unwrap(), None);
+	}
+
+	#[test]
+	fn go_build_info_follows_string_headers() {
+		let elf = fixture("go-buildinfo");
+		let info = elf.go_build_info().unwrap().unwrap();
+		assert_eq!(info.version, "go1.17.2");
+		assert_eq!(
+			info.module_info,
+			"path\texample.com/hello\nmod\texample.com/hello\t(devel)\t\n"
+		);
+
+		// a corrupt length is caught before it's allocated
+		let section = elf.section_by_name(".go.buildinfo").unwrap().unwrap();
+		let mut bytes = fixture_bytes("go-buildinfo");
+		let version = read_u64_at(&bytes, section.offset as usize + BUILD_INFO_MAGIC.len() + 2);
+		let length = elf.virtual_to_offset(version).unwrap() as usize + 8;
+		write_u64_at(&mut bytes, length, u64::MAX / 2);
+
+		let error = Elf::try_from(bytes.as_slice()).unwrap().go_build_info().unwrap_err();
+		assert_eq!(error.kind(), &ErrorKind::Malformed);
+		assert_eq!(
+			error.to_string(),
+			format!("Go string at {version:#x} is longer than the segment holding it")
+		);
+	}
+}
@@ -0,0 +1,138 @@
+use super::{
+	dynamic::{find_dynamic_value, DynamicTag},
+	Elf, Result, SectionType, Symbol,
+};
+use crate::error::Error;
+
+const HASH_WORD_SIZE: usize = 4;
+const GNU_HASH_HEADER_SIZE: usize = 16;
+const GNU_HASH_BLOOM_WORD_SIZE: usize = 8;
+
+struct GnuHashLayout {
+	bucket_count: usize,
+	symbol_offset: usize,
+	// offset of the chains from the start of the table
+	chains: usize,
+}
+
+impl Elf {
+	// the dynamic loader only looks up symbols from `symoffset` onward, the ones before are local
+	// or undefined
+	pub fn gnu_hash_exported_symbols(&self) -> Result<Vec<Symbol>> {
+		let Some(data) = self.hash_table_data(DynamicTag::GnuHash, SectionType::GnuHash)? else {
+			return Ok(Vec::new());
+		};
+		let symbol_offset = self.gnu_hash_layout(data)?.symbol_offset;
+		// an empty table (e.g. in executables that export nothing) still has a `symoffset`
+		if self.gnu_hash_symbol_count(data)?.is_none() {
+			return Ok(Vec::new());
+		}
+
+		let table = self
+			.sections
+			.iter()
+			.find(|it| it.kind == SectionType::LinkerSymbolTable)
+			.ok_or("No dynamic symbol table to go with the GNU hash table")?;
+
+		Ok(self.section_symbols(table)?.into_iter().skip(symbol_offset).collect())
+	}
+
+	// `None` if there's no hash table, or only a GNU one without any hashed symbols, since that
+	// doesn't tell how many unhashed ones precede them
+	pub fn dynamic_symbol_count(&self) -> Result<Option<usize>> {
+		if let Some(data) = self.hash_table_data(DynamicTag::Hash, SectionType::HashTable)? {
+			let mut reader = self.reader_for(data);
+			let _bucket_count = reader.read_u32()?;
+			return Ok(Some(reader.read_u32()? as usize));
+		}
+
+		match self.hash_table_data(DynamicTag::GnuHash, SectionType::GnuHash)? {
+			Some(data) => self.gnu_hash_symbol_count(data),
+			None => Ok(None),
+		}
+	}
+
+	fn gnu_hash_symbol_count(&self, data: &[u8]) -> Result<Option<usize>> {
+		let layout = self.gnu_hash_layout(data)?;
+
+		let mut reader = self.reader_for(data);
+		reader.seek(layout.chains - layout.bucket_count * HASH_WORD_SIZE)?;
+		let mut last = 0;
+		for _ in 0..layout.bucket_count {
+			last = last.max(reader.read_u32()? as usize);
+		}
+
+		if last < layout.symbol_offset {
+			return Ok(None);
+		}
+
+		// the last chain ends with the highest symbol, marked by the lowest bit of its hash
+		reader.seek(layout.chains + (last - layout.symbol_offset) * HASH_WORD_SIZE)?;
+		let mut index = last;
+		while reader.read_u32()? & 1 == 0 {
+			index += 1;
+		}
+
+		Ok(Some(index + 1))
+	}
+
+	// the sizes of the tables in bytes, for when there's no section header to tell
+	pub(super) fn hash_size(&self) -> Result<Option<u64>> {
+		let Some(data) = self.hash_table_data(DynamicTag::Hash, SectionType::HashTable)? else {
+			return Ok(None);
+		};
+
+		let mut reader = self.reader_for(data);
+		let bucket_count = reader.read_u32()? as usize;
+		let chain_count = reader.read_u32()? as usize;
+		Ok(Some(((2 + bucket_count + chain_count) * HASH_WORD_SIZE) as u64))
+	}
+
+	pub(super) fn gnu_hash_size(&self) -> Result<Option<u64>> {
+		let Some(data) = self.hash_table_data(DynamicTag::GnuHash, SectionType::GnuHash)? else {
+			return Ok(None);
+		};
+
+		let layout = self.gnu_hash_layout(data)?;
+		let chained = self.gnu_hash_symbol_count(data)?.map_or(0, |it| it - layout.symbol_offset);
+		Ok(Some((layout.chains + chained * HASH_WORD_SIZE) as u64))
+	}
+
+	fn gnu_hash_layout(&self, data: &[u8]) -> Result<GnuHashLayout> {
+		let mut reader = self.reader_for(data);
+		let bucket_count = reader.read_u32()? as usize;
+		let symbol_offset = reader.read_u32()? as usize;
+		let bloom_size = reader.read_u32()? as usize;
+
+		let chains = GNU_HASH_HEADER_SIZE
+			+ bloom_size * GNU_HASH_BLOOM_WORD_SIZE
+			+ bucket_count * HASH_WORD_SIZE;
+		Ok(GnuHashLayout {
+			bucket_count,
+			symbol_offset,
+			chains,
+		})
+	}
+
+	// with only the address from the dynamic table the size is unknown, so the data runs to the
+	// end of the segment
+	fn hash_table_data(&self, tag: DynamicTag, kind: SectionType) -> Result<Option<&[u8]>> {
+		let entries = self.dynamic_entries()?;
+
+		if let Some(address) = find_dynamic_value(&entries, tag) {
+			let segment = self
+				.loadable_segments()
+				.find(|it| {
+					address >= it.virtual_address && address - it.virtual_address < it.file_size
+				})
+				.ok_or_else(|| {
+					Error::malformed(format!("Hash table at {address:#x} is not mapped"))
+				})?;
+			let start = (address - segment.virtual_address) as usize;
+			return Ok(self.segment_data(segment)?.get(start..));
+		}
+
+		let section = self.sections.iter().find(|it| it.kind == kind);
+		section.map(|it| self.section_data(it)).transpose()
+	}
+}
@@ -0,0 +1,52 @@
+use super::{Elf, Result, SectionType};
+use crate::error::Error;
+
+const ABI_FLAGS_SIZE: usize = 24;
+
+// `Elf_MIPS_ABIFlags_v0`, register sizes are encoded (0 none, 1 32-bit, 2 64-bit, 3 128-bit)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MipsAbiFlags {
+	pub version: u16,
+	pub isa_level: u8,
+	pub isa_revision: u8,
+	pub gpr_size: u8,
+	pub cpr1_size: u8,
+	pub cpr2_size: u8,
+	pub fp_abi: u8,
+	pub isa_extension: u32,
+	pub ases: u32,
+	pub flags1: u32,
+	pub flags2: u32,
+}
+
+impl Elf {
+	pub fn mips_abi_flags(&self) -> Result<Option<MipsAbiFlags>> {
+		let Some(section) = self.sections.iter().find(|it| it.kind == SectionType::MipsAbiFlags)
+		else {
+			return Ok(None);
+		};
+
+		let data = self.section_data(section)?;
+		if data.len() < ABI_FLAGS_SIZE {
+			return Err(Error::malformed(format!(
+				"MIPS ABI flags need {ABI_FLAGS_SIZE} bytes, but the section has {}",
+				data.len()
+			)));
+		}
+
+		let mut reader = self.reader_for(data);
+		Ok(Some(MipsAbiFlags {
+			version: reader.read_u16()?,
+			isa_level: reader.read_u8()?,
+			isa_revision: reader.read_u8()?,
+			gpr_size: reader.read_u8()?,
+			cpr1_size: reader.read_u8()?,
+			cpr2_size: reader.read_u8()?,
+			fp_abi: reader.read_u8()?,
+			isa_extension: reader.read_u32()?,
+			ases: reader.read_u32()?,
+			flags1: reader.read_u32()?,
+			flags2: reader.read_u32()?,
+		}))
+	}
+}
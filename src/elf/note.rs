@@ -0,0 +1,248 @@
+use super::{Elf, ProgramType, Result, SectionType};
+use crate::{
+	consts::{NT_GNU_ABI_TAG, NT_GNU_PROPERTY_TYPE_0},
+	reader::Reader,
+};
+
+const ELF_NOTE_OS_LINUX: u32 = 0;
+// the property array is padded to 8 bytes in ELF64
+const GNU_PROPERTY_ALIGNMENT: usize = 8;
+
+pub struct GnuProperty {
+	pub kind: u32,
+	pub data: Vec<u8>,
+}
+
+pub struct Note {
+	pub name: String,
+	pub kind: u32,
+	pub description: Vec<u8>,
+}
+
+impl Elf {
+	pub fn notes(&self) -> Result<Vec<Note>> {
+		let mut notes = Vec::new();
+
+		let note_sections = self.sections.iter().filter(|it| it.kind == SectionType::Note);
+		for section in note_sections.clone() {
+			let alignment = section.address_alignment as usize;
+			for note in NoteIter::new(self.reader_for(self.section_data(section)?), alignment) {
+				notes.push(note?);
+			}
+		}
+
+		// files without section headers (e.g. core dumps) only have the note segments
+		if note_sections.count() == 0 {
+			notes = self.segment_notes()?;
+		}
+
+		Ok(notes)
+	}
+
+	pub(super) fn segment_notes(&self) -> Result<Vec<Note>> {
+		let mut notes = Vec::new();
+
+		for segment in self.segments_of_type(ProgramType::Note) {
+			let alignment = segment.alignment as usize;
+			for note in NoteIter::new(self.reader_for(self.segment_data(segment)?), alignment) {
+				notes.push(note?);
+			}
+		}
+
+		Ok(notes)
+	}
+
+	// stripped of section headers, the properties can still be found through `PT_GNU_PROPERTY`
+	pub fn gnu_properties(&self) -> Result<Vec<GnuProperty>> {
+		let (data, alignment) = match self.section_by_name(".note.gnu.property")? {
+			Some(section) => (self.section_data(section)?, section.address_alignment),
+			None => match self.segments_of_type(ProgramType::GnuProperty).next() {
+				Some(segment) => (self.segment_data(segment)?, segment.alignment),
+				None => return Ok(Vec::new()),
+			},
+		};
+
+		let mut properties = Vec::new();
+		for note in NoteIter::new(self.reader_for(data), alignment as usize) {
+			let note = note?;
+			if note.name != "GNU" || note.kind != NT_GNU_PROPERTY_TYPE_0 {
+				continue;
+			}
+
+			let mut reader = self.reader_for(&note.description);
+			while !reader.is_empty() {
+				let kind = reader.read_u32()?;
+				let size = reader.read_u32()? as usize;
+				let data = reader.read_bytes(size)?.to_vec();
+				reader.align(GNU_PROPERTY_ALIGNMENT)?;
+
+				properties.push(GnuProperty { kind, data });
+			}
+		}
+
+		Ok(properties)
+	}
+
+	pub fn min_kernel_version(&self) -> Result<Option<(u32, u32, u32)>> {
+		let notes = self.notes()?;
+		let Some(note) = notes.iter().find(|it| it.name == "GNU" && it.kind == NT_GNU_ABI_TAG)
+		else {
+			return Ok(None);
+		};
+
+		let mut reader = self.reader_for(&note.description);
+		let os = reader.read_u32()?;
+		let major = reader.read_u32()?;
+		let minor = reader.read_u32()?;
+		let patch = reader.read_u32()?;
+
+		if os != ELF_NOTE_OS_LINUX {
+			return Ok(None);
+		}
+
+		Ok(Some((major, minor, patch)))
+	}
+}
+
+pub struct NoteIter<'a> {
+	reader: Reader<'a>,
+	alignment: usize,
+	failed: bool,
+}
+
+impl<'a> NoteIter<'a> {
+	// the reader decides the byte order, e.g. `Reader::new(data, true)` or `Elf::reader_for`
+	pub fn new(reader: Reader<'a>, alignment: usize) -> Self {
+		NoteIter {
+			reader,
+			// only 8-byte aligned notes (e.g. `.note.gnu.property`) use 8-byte padding
+			alignment: if alignment == 8 { 8 } else { 4 },
+			failed: false,
+		}
+	}
+
+	fn read_note(&mut self) -> Result<Note> {
+		let reader = &mut self.reader;
+
+		let name_size = reader.read_u32()? as usize;
+		let description_size = reader.read_u32()? as usize;
+		let kind = reader.read_u32()?;
+
+		let name = reader.read_bytes(name_size)?;
+		// the name is padded with nulls to its size, e.g. Go uses 4 bytes for `Go\0\0`
+		let length = name.iter().rposition(|it| *it != 0).map_or(0, |it| it + 1);
+		let name = String::from_utf8(name[..length].to_vec())?;
+		reader.align(self.alignment)?;
+
+		let description = reader.read_bytes(description_size)?.to_vec();
+		reader.align(self.alignment)?;
+
+		Ok(Note {
+			name,
+			kind,
+			description,
+		})
+	}
+}
+
+impl Iterator for NoteIter<'_> {
+	type Item = Result<Note>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		// the sizes of a broken note can't be trusted, so there's no finding the next one
+		if self.failed || self.reader.is_empty() {
+			return None;
+		}
+
+		let note = self.read_note();
+		self.failed = note.is_err();
+		Some(note)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		consts::GNU_PROPERTY_X86_ISA_1_NEEDED,
+		testing::{fixture, fixture_bytes, write_u16_at, write_u64_at},
+	};
+
+	fn properties(elf: &Elf) -> Vec<(u32, Vec<u8>)> {
+		let properties = elf.gnu_properties().unwrap();
+		properties.into_iter().map(|it| (it.kind, it.data)).collect()
+	}
+
+	#[test]
+	fn gnu_properties_fall_back_to_the_segment() {
+		let expected = properties(&fixture("sample"));
+		assert_eq!(expected.len(), 1);
+		assert_eq!(expected[0].0, GNU_PROPERTY_X86_ISA_1_NEEDED);
+
+		// drop the section header table
+		let mut bytes = fixture_bytes("sample");
+		write_u64_at(&mut bytes, 0x28, 0);
+		write_u16_at(&mut bytes, 0x3C, 0);
+		write_u16_at(&mut bytes, 0x3E, 0);
+
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+		assert!(elf.sections().is_empty());
+		assert_eq!(properties(&elf), expected);
+	}
+
+	#[test]
+	fn note_names_are_trimmed_of_all_padding() {
+		let mut data = Vec::new();
+		for (name, kind) in [(&b"Go\0\0"[..], 4u32), (b"GNU\0", 3), (b"\0\0\0\0", 1)] {
+			data.extend((name.len() as u32).to_be_bytes());
+			data.extend(2u32.to_be_bytes());
+			data.extend(kind.to_be_bytes());
+			data.extend(name);
+			data.extend([0xAB, 0xCD, 0, 0]);
+		}
+
+		let notes = NoteIter::new(Reader::new(&data, false), 4);
+		let notes = notes.map(|it| it.map(|it| (it.name, it.kind, it.description)));
+		assert_eq!(
+			notes.collect::<Result<Vec<_>>>().unwrap(),
+			[
+				("Go".to_owned(), 4, vec![0xAB, 0xCD]),
+				("GNU".to_owned(), 3, vec![0xAB, 0xCD]),
+				(String::new(), 1, vec![0xAB, 0xCD]),
+			]
+		);
+	}
+
+	#[test]
+	fn eight_byte_aligned_notes_pad_their_descriptions() {
+		// laid out like `.note.gnu.property`, where the 16 bytes of header and `GNU\0` happen to
+		// be aligned already, but a 12-byte description isn't
+		let mut data = Vec::new();
+		for description in [&[1u8; 12][..], &[2; 8]] {
+			data.extend(4u32.to_le_bytes());
+			data.extend((description.len() as u32).to_le_bytes());
+			data.extend(5u32.to_le_bytes());
+			data.extend(b"GNU\0");
+			data.extend(description);
+			data.resize(data.len().next_multiple_of(8), 0);
+		}
+		assert_eq!(data.len(), 56);
+
+		let notes = NoteIter::new(Reader::new(&data, true), 8);
+		let notes = notes.map(|it| it.map(|it| (it.name, it.kind, it.description)));
+		assert_eq!(
+			notes.collect::<Result<Vec<_>>>().unwrap(),
+			[
+				("GNU".to_owned(), 5, vec![1; 12]),
+				("GNU".to_owned(), 5, vec![2; 8])
+			]
+		);
+
+		// with 4-byte padding the first note's padding is read as the name size of the next one,
+		// which then takes its kind from the real second note's description size
+		let notes = NoteIter::new(Reader::new(&data, true), 4).collect::<Vec<_>>();
+		let second = notes[1].as_ref().unwrap();
+		assert_eq!((second.name.as_str(), second.kind), ("", 8));
+		assert!(notes.last().unwrap().is_err());
+	}
+}
@@ -0,0 +1,157 @@
+use object::{read::elf::ElfFile64, Architecture, SectionKind, SymbolKind, SymbolScope};
+
+use super::{
+	Elf, Machine, Result, Section, SectionFlags, SectionType, Symbol, SymbolBinding, SymbolType,
+	SymbolVisibility,
+};
+use crate::{
+	consts::{SHN_UNDEF, STT_GNU_IFUNC},
+	error::Error,
+};
+
+// `object`'s reader traits are sealed, so they can't be implemented for these types. the file is
+// handed to its ELF reader instead, and the conversions below agree with what that reader reports
+impl Elf {
+	// written out first, `object` reads from a byte slice and the bodies may have been edited
+	pub fn to_object<'a>(&self, buffer: &'a mut Vec<u8>) -> Result<ElfFile64<'a>> {
+		buffer.clear();
+		self.write(&mut *buffer)?;
+		ElfFile64::parse(buffer.as_slice()).map_err(|error| Error::malformed(error.to_string()))
+	}
+}
+
+// only 64-bit files are parsed, so e.g. MIPS is always MIPS64. `object` also tells n32 files
+// apart by their header flags
+impl From<Machine> for Architecture {
+	fn from(machine: Machine) -> Self {
+		match machine {
+			Machine::X86 => Architecture::I386,
+			Machine::Mips => Architecture::Mips64,
+			Machine::Arm => Architecture::Arm,
+			Machine::Amd64 => Architecture::X86_64,
+			Machine::Arm64 => Architecture::Aarch64,
+			Machine::RiscV => Architecture::Riscv64,
+			Machine::Other(_) => Architecture::Unknown,
+		}
+	}
+}
+
+impl From<&Section> for SectionKind {
+	fn from(section: &Section) -> Self {
+		let flags = section.section_flags();
+		match section.kind {
+			SectionType::ProgramData if section.is_allocated() => {
+				if flags.contains(SectionFlags::EXECUTE) {
+					SectionKind::Text
+				} else if flags.contains(SectionFlags::TLS) {
+					SectionKind::Tls
+				} else if flags.contains(SectionFlags::WRITE) {
+					SectionKind::Data
+				} else if flags.contains(SectionFlags::STRINGS) {
+					SectionKind::ReadOnlyString
+				} else {
+					SectionKind::ReadOnlyData
+				}
+			}
+			SectionType::ProgramData if flags.contains(SectionFlags::STRINGS) => {
+				SectionKind::OtherString
+			}
+			SectionType::ProgramData => SectionKind::Other,
+			SectionType::NoBits if flags.contains(SectionFlags::TLS) => {
+				SectionKind::UninitializedTls
+			}
+			SectionType::NoBits => SectionKind::UninitializedData,
+			SectionType::Note => SectionKind::Note,
+			SectionType::Null
+			| SectionType::SymbolTable
+			| SectionType::StringTable
+			| SectionType::RelocationEntriesWithAddends
+			| SectionType::HashTable
+			| SectionType::Dynamic
+			| SectionType::RelocationEntries
+			| SectionType::LinkerSymbolTable
+			| SectionType::Group
+			| SectionType::SymbolTableNameIndices
+			| SectionType::Relr => SectionKind::Metadata,
+			_ => SectionKind::Unknown,
+		}
+	}
+}
+
+impl From<SymbolType> for SymbolKind {
+	fn from(kind: SymbolType) -> Self {
+		match kind {
+			SymbolType::Object | SymbolType::Common => SymbolKind::Data,
+			SymbolType::Function | SymbolType::OperatingSystem(STT_GNU_IFUNC) => SymbolKind::Text,
+			SymbolType::Section => SymbolKind::Section,
+			SymbolType::File => SymbolKind::File,
+			SymbolType::ThreadLocalStorage => SymbolKind::Tls,
+			_ => SymbolKind::Unknown,
+		}
+	}
+}
+
+// hidden symbols are only visible to the static linker, undefined ones could be anything
+impl From<&Symbol> for SymbolScope {
+	fn from(symbol: &Symbol) -> Self {
+		if symbol.section_index == SHN_UNDEF {
+			return SymbolScope::Unknown;
+		}
+
+		match symbol.binding {
+			SymbolBinding::Local => SymbolScope::Compilation,
+			SymbolBinding::Global | SymbolBinding::Weak
+				if symbol.visibility() == SymbolVisibility::Hidden =>
+			{
+				SymbolScope::Linkage
+			}
+			SymbolBinding::Global | SymbolBinding::Weak => SymbolScope::Dynamic,
+			_ => SymbolScope::Unknown,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use object::{Object, ObjectSection, ObjectSymbol};
+
+	use super::*;
+	use crate::testing::fixture;
+
+	#[test]
+	fn conversions_agree_with_object() {
+		for name in ["sample", "sample.o", "mips64-be.o"] {
+			let elf = fixture(name);
+			let mut buffer = Vec::new();
+			let file = elf.to_object(&mut buffer).unwrap();
+
+			assert_eq!(Architecture::from(elf.header.machine), file.architecture(), "{name}");
+
+			let sections: Vec<_> = file.sections().collect();
+			assert_eq!(sections.len() + 1, elf.sections.len(), "{name}");
+			for theirs in sections {
+				let ours = &elf.sections[theirs.index().0];
+				assert_eq!(theirs.name().unwrap(), elf.section_name(ours).unwrap());
+				assert_eq!(theirs.address(), ours.address);
+				assert_eq!(theirs.size(), ours.size);
+				assert_eq!(SectionKind::from(ours), theirs.kind(), "{:?}", theirs.name());
+			}
+
+			let pairs = [
+				(elf.static_symbols().unwrap(), file.symbols().collect::<Vec<_>>()),
+				(elf.dynamic_symbols().unwrap(), file.dynamic_symbols().collect()),
+			];
+			for (symbols, object_symbols) in pairs {
+				for theirs in object_symbols {
+					let ours = &symbols[theirs.index().0];
+					assert_eq!(theirs.name().unwrap(), ours.name);
+					// object reports no address for common symbols instead of their alignment
+					let address = if ours.is_common() { 0 } else { ours.value };
+					assert_eq!(theirs.address(), address, "{}", ours.name);
+					assert_eq!(SymbolKind::from(ours.kind), theirs.kind(), "{}", ours.name);
+					assert_eq!(SymbolScope::from(ours), theirs.scope(), "{}", ours.name);
+				}
+			}
+		}
+	}
+}
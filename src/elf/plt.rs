@@ -0,0 +1,129 @@
+use super::{Elf, Machine, Result};
+
+const X86_PLT_HEADER_SIZE: u64 = 16;
+const X86_PLT_ENTRY_SIZE: u64 = 16;
+const AARCH64_PLT_HEADER_SIZE: u64 = 32;
+const AARCH64_PLT_ENTRY_SIZE: u64 = 16;
+
+impl Machine {
+	// the size of the stubs GNU ld emits, other linkers may differ
+	pub fn plt_entry_size(&self) -> Option<u64> {
+		match self {
+			Machine::X86 | Machine::Amd64 => Some(X86_PLT_ENTRY_SIZE),
+			Machine::Arm64 => Some(AARCH64_PLT_ENTRY_SIZE),
+			_ => None,
+		}
+	}
+
+	// the lazy binding stub in front of the first entry
+	pub fn plt_header_size(&self) -> Option<u64> {
+		match self {
+			Machine::X86 | Machine::Amd64 => Some(X86_PLT_HEADER_SIZE),
+			Machine::Arm64 => Some(AARCH64_PLT_HEADER_SIZE),
+			_ => None,
+		}
+	}
+}
+
+pub struct PltEntry {
+	pub address: u64,
+	pub got_address: u64,
+	pub name: Option<String>,
+}
+
+impl Elf {
+	// the stubs are in the same order as the PLT relocations, after the lazy binding header
+	pub fn plt_entries(&self) -> Result<Vec<PltEntry>> {
+		let machine = self.header.machine;
+		let (Some(header_size), Some(entry_size)) =
+			(machine.plt_header_size(), machine.plt_entry_size())
+		else {
+			return Err(format!("PLT layout of {machine:?} is not supported").into());
+		};
+
+		// with IBT the stubs that get called live in `.plt.sec`, and `.plt` only has the lazy
+		// binding trampolines
+		let (section, header_size) = match self.section_by_name(".plt.sec")? {
+			Some(section) if machine == Machine::Amd64 => (Some(section), 0),
+			_ => (self.section_by_name(".plt")?, header_size),
+		};
+
+		let Some(section) = section else {
+			return Ok(Vec::new());
+		};
+
+		let relocations = self.plt_relocations()?;
+		if header_size + relocations.len() as u64 * entry_size > section.size {
+			return Err(format!(
+				"PLT of {:#x} bytes is too small for {} relocations",
+				section.size,
+				relocations.len()
+			)
+			.into());
+		}
+
+		let entries =
+			relocations.into_iter().enumerate().map(|(index, (relocation, name))| PltEntry {
+				address: section.address + header_size + index as u64 * entry_size,
+				got_address: relocation.offset,
+				name,
+			});
+
+		Ok(entries.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{consts::R_X86_64_JUMP_SLOT, testing::fixture};
+
+	#[test]
+	fn stub_sizes_match_gnu_ld() {
+		assert_eq!(Machine::Amd64.plt_header_size(), Some(16));
+		assert_eq!(Machine::Amd64.plt_entry_size(), Some(16));
+		assert_eq!(Machine::Arm64.plt_header_size(), Some(32));
+		assert_eq!(Machine::Arm64.plt_entry_size(), Some(16));
+		assert_eq!(Machine::RiscV.plt_entry_size(), None);
+	}
+
+	#[test]
+	fn plt_entries_follow_the_jump_slots() {
+		// the `<name@plt>` labels of `objdump -d -j .plt`
+		let cases = [
+			("sample", vec![(0x1030, "puts")]),
+			(
+				"hardened",
+				vec![
+					(0x1030, "puts"),
+					(0x1040, "__stack_chk_fail"),
+					(0x1050, "__sprintf_chk"),
+				],
+			),
+		];
+
+		for (name, expected) in cases {
+			let elf = fixture(name);
+			let entries = elf.plt_entries().unwrap();
+			let plt = elf.section_by_name(".plt").unwrap().unwrap();
+			let header_size = elf.header.machine.plt_header_size().unwrap();
+			let entry_size = elf.header.machine.plt_entry_size().unwrap();
+
+			let relocations = elf.plt_relocations().unwrap();
+			assert!(relocations.iter().all(|(it, _)| it.kind == R_X86_64_JUMP_SLOT));
+			assert_eq!(entries.len(), expected.len(), "{name}");
+
+			for (index, (entry, (address, symbol))) in entries.iter().zip(expected).enumerate() {
+				assert_eq!(entry.address, plt.address + header_size + index as u64 * entry_size);
+				assert_eq!(entry.address, address);
+				assert_eq!(entry.name.as_deref(), Some(symbol));
+				assert_eq!(entry.got_address, relocations[index].0.offset);
+			}
+		}
+	}
+
+	#[test]
+	fn objects_have_no_plt() {
+		assert!(fixture("sample.o").plt_entries().unwrap().is_empty());
+	}
+}
@@ -0,0 +1,206 @@
+use std::cell::OnceCell;
+
+use super::{
+	dynamic::{find_dynamic_value, DynamicTag},
+	Elf, ProgramType, Result, Section, SectionType,
+};
+use crate::consts::{SHF_ALLOC, SHF_WRITE};
+
+const SYMBOL_ENTRY_SIZE: u64 = 24;
+const RELA_ENTRY_SIZE: u64 = 24;
+const RELR_ENTRY_SIZE: u64 = 8;
+const DYNAMIC_ENTRY_SIZE: u64 = 16;
+const HASH_WORD_SIZE: u64 = 4;
+
+// sections are numbered in the order they're pushed, with the null section first
+const DYNSYM_INDEX: u32 = 1;
+const DYNSTR_INDEX: u32 = 2;
+
+impl Elf {
+	// synthesizes the sections a loader cares about from the dynamic entries, for files whose
+	// section headers were stripped. the last section is a `.shstrtab` naming the others
+	pub fn reconstruct_sections(&self) -> Result<Vec<Section>> {
+		let entries = self.dynamic_entries()?;
+		if entries.is_empty() {
+			return Err("No dynamic entries to reconstruct sections from".into());
+		}
+
+		let mut builder = SectionBuilder {
+			elf: self,
+			names: vec![0],
+			sections: Vec::new(),
+		};
+		builder.sections.push(null_section());
+
+		let strings = find_dynamic_value(&entries, DynamicTag::StringTable)
+			.ok_or("Dynamic section has no string table")?;
+		let strings_size = find_dynamic_value(&entries, DynamicTag::StringTableSize)
+			.ok_or("Dynamic section has no string table size")?;
+		let symbols = find_dynamic_value(&entries, DynamicTag::SymbolTable)
+			.ok_or("Dynamic section has no symbol table")?;
+		let symbol_entry_size =
+			find_dynamic_value(&entries, DynamicTag::SymbolEntrySize).unwrap_or(SYMBOL_ENTRY_SIZE);
+		// as a last resort, the string table usually follows the symbol table directly
+		let symbol_count = match self.dynamic_symbol_count()? {
+			Some(count) => count as u64,
+			None if strings > symbols && symbol_entry_size > 0 => {
+				(strings - symbols) / symbol_entry_size
+			}
+			None => return Err("Could not determine the number of dynamic symbols".into()),
+		};
+
+		let dynsym = builder.push(
+			".dynsym",
+			SectionType::LinkerSymbolTable,
+			symbols,
+			symbol_count * symbol_entry_size,
+		)?;
+		dynsym.link = DYNSTR_INDEX;
+		// the loader only looks at global symbols, everything past the null symbol may be one
+		dynsym.info = 1;
+		dynsym.address_alignment = 8;
+		dynsym.entry_size = symbol_entry_size;
+
+		builder.push(".dynstr", SectionType::StringTable, strings, strings_size)?;
+
+		if let Some(address) = find_dynamic_value(&entries, DynamicTag::Hash) {
+			let size = self.hash_size()?.unwrap_or_default();
+			let hash = builder.push(".hash", SectionType::HashTable, address, size)?;
+			hash.link = DYNSYM_INDEX;
+			hash.address_alignment = 8;
+			hash.entry_size = HASH_WORD_SIZE;
+		}
+
+		if let Some(address) = find_dynamic_value(&entries, DynamicTag::GnuHash) {
+			let size = self.gnu_hash_size()?.unwrap_or_default();
+			let hash = builder.push(".gnu.hash", SectionType::GnuHash, address, size)?;
+			hash.link = DYNSYM_INDEX;
+			hash.address_alignment = 8;
+		}
+
+		let relocations = [
+			(
+				".rela.dyn",
+				DynamicTag::RelocationsWithAddends,
+				DynamicTag::RelocationsWithAddendsSize,
+			),
+			(".rela.plt", DynamicTag::PltRelocations, DynamicTag::PltRelocationSize),
+		];
+		for (name, address_tag, size_tag) in relocations {
+			let address = find_dynamic_value(&entries, address_tag);
+			let size = find_dynamic_value(&entries, size_tag);
+			if let (Some(address), Some(size)) = (address, size) {
+				let rela =
+					builder.push(name, SectionType::RelocationEntriesWithAddends, address, size)?;
+				rela.link = DYNSYM_INDEX;
+				rela.address_alignment = 8;
+				rela.entry_size = RELA_ENTRY_SIZE;
+			}
+		}
+
+		let address = find_dynamic_value(&entries, DynamicTag::Relr);
+		let size = find_dynamic_value(&entries, DynamicTag::RelrSize);
+		if let (Some(address), Some(size)) = (address, size) {
+			let relr = builder.push(".relr.dyn", SectionType::Relr, address, size)?;
+			relr.address_alignment = 8;
+			relr.entry_size = RELR_ENTRY_SIZE;
+		}
+
+		if let Some(segment) = self.segments_of_type(ProgramType::Dynamic).next() {
+			let dynamic = builder.push(
+				".dynamic",
+				SectionType::Dynamic,
+				segment.virtual_address,
+				segment.file_size,
+			)?;
+			dynamic.flags |= SHF_WRITE;
+			dynamic.link = DYNSTR_INDEX;
+			dynamic.address_alignment = 8;
+			dynamic.entry_size = DYNAMIC_ENTRY_SIZE;
+		}
+
+		Ok(builder.finish())
+	}
+}
+
+struct SectionBuilder<'a> {
+	elf: &'a Elf,
+	names: Vec<u8>,
+	sections: Vec<Section>,
+}
+
+impl SectionBuilder<'_> {
+	fn push(
+		&mut self,
+		name: &str,
+		kind: SectionType,
+		address: u64,
+		size: u64,
+	) -> Result<&mut Section> {
+		let offset = self
+			.elf
+			.virtual_to_offset(address)
+			.ok_or_else(|| format!("Section {name} at {address:#x} is not mapped"))?;
+		let data = self
+			.elf
+			.virtual_data(address, size)?
+			.ok_or_else(|| format!("Section {name} at {address:#x} is not mapped"))?;
+
+		let name_index = self.add_name(name);
+		self.sections.push(Section {
+			name_index,
+			kind,
+			flags: SHF_ALLOC,
+			address,
+			offset,
+			size,
+			link: 0,
+			info: 0,
+			address_alignment: 1,
+			entry_size: 0,
+			data: OnceCell::from(data.to_vec()),
+		});
+		Ok(self.sections.last_mut().unwrap())
+	}
+
+	fn add_name(&mut self, name: &str) -> usize {
+		let index = self.names.len();
+		self.names.extend_from_slice(name.as_bytes());
+		self.names.push(0);
+		index
+	}
+
+	fn finish(mut self) -> Vec<Section> {
+		let name_index = self.add_name(".shstrtab");
+		self.sections.push(Section {
+			name_index,
+			kind: SectionType::StringTable,
+			flags: 0,
+			address: 0,
+			offset: 0,
+			size: self.names.len() as u64,
+			link: 0,
+			info: 0,
+			address_alignment: 1,
+			entry_size: 0,
+			data: OnceCell::from(self.names),
+		});
+		self.sections
+	}
+}
+
+fn null_section() -> Section {
+	Section {
+		name_index: 0,
+		kind: SectionType::Null,
+		flags: 0,
+		address: 0,
+		offset: 0,
+		size: 0,
+		link: 0,
+		info: 0,
+		address_alignment: 0,
+		entry_size: 0,
+		data: OnceCell::from(Vec::new()),
+	}
+}
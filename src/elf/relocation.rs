@@ -0,0 +1,325 @@
+use super::{
+	dynamic::{find_dynamic_value, DynamicEntry, DynamicTag},
+	Elf, Machine, Result, SectionType, Warning,
+};
+use crate::consts::{R_AARCH64_NONE, R_AARCH64_RELATIVE, R_X86_64_NONE, R_X86_64_RELATIVE};
+
+const REL_ENTRY_SIZE: u64 = 16;
+const RELA_ENTRY_SIZE: u64 = 24;
+const RELR_WORD_SIZE: u64 = 8;
+
+pub struct Rela {
+	pub offset: u64,
+	pub kind: u32,
+	pub symbol_index: u32,
+	pub addend: i64,
+}
+
+// both forms of relocation entries, `addend` is `None` for `SHT_REL`, whose addend is whatever the
+// relocated location already holds
+#[derive(Debug, PartialEq)]
+pub struct Relocation {
+	pub offset: u64,
+	pub kind: u32,
+	pub symbol_index: u32,
+	pub symbol_name: Option<String>,
+	pub addend: Option<i64>,
+	// the section being relocated, dynamic relocations usually don't name one
+	pub target_section: Option<u32>,
+}
+
+impl Elf {
+	pub fn all_relocations(&self) -> Result<Vec<Relocation>> {
+		let mut relocations = Vec::new();
+
+		let relocation_sections = self.sections.iter().filter(|it| {
+			matches!(
+				it.kind,
+				SectionType::RelocationEntries | SectionType::RelocationEntriesWithAddends
+			)
+		});
+		for section in relocation_sections {
+			let symbols = match self.sections.get(section.link as usize) {
+				Some(it) if it.kind != SectionType::Null => self.section_symbols(it)?,
+				_ => Vec::new(),
+			};
+			let target_section = (section.info != 0).then_some(section.info);
+
+			let has_addend = section.kind == SectionType::RelocationEntriesWithAddends;
+			let entries = if has_addend {
+				self.read_rela(self.section_data(section)?)?
+			} else {
+				self.read_rel(self.section_data(section)?)?
+			};
+			for rela in entries {
+				let symbol_name = match rela.symbol_index {
+					0 => None,
+					index => {
+						Some(symbols.get(index as usize).map(|it| it.name.clone()).ok_or_else(
+							|| format!("Relocation refers to missing symbol {index}"),
+						)?)
+					}
+				};
+
+				relocations.push(Relocation {
+					offset: rela.offset,
+					kind: rela.kind,
+					symbol_index: rela.symbol_index,
+					symbol_name,
+					addend: has_addend.then_some(rela.addend),
+					target_section,
+				});
+			}
+		}
+
+		Ok(relocations)
+	}
+
+	pub fn plt_relocations(&self) -> Result<Vec<(Rela, Option<String>)>> {
+		let entries = self.dynamic_entries()?;
+
+		let relocation_type = find_dynamic_value(&entries, DynamicTag::PltRelocationType);
+		if relocation_type.is_some_and(|it| it != u64::from(DynamicTag::RelocationsWithAddends)) {
+			return Err("PLT relocations without addends are not supported".into());
+		}
+
+		self.bound_relocations(
+			&entries,
+			DynamicTag::PltRelocations,
+			DynamicTag::PltRelocationSize,
+			".rela.plt",
+		)
+	}
+
+	pub fn dynamic_relocations(&self) -> Result<Vec<(Rela, Option<String>)>> {
+		let entries = self.dynamic_entries()?;
+
+		self.bound_relocations(
+			&entries,
+			DynamicTag::RelocationsWithAddends,
+			DynamicTag::RelocationsWithAddendsSize,
+			".rela.dyn",
+		)
+	}
+
+	pub fn relr_relocations(&self) -> Result<Vec<u64>> {
+		let entries = self.dynamic_entries()?;
+
+		let address = find_dynamic_value(&entries, DynamicTag::Relr);
+		let size = find_dynamic_value(&entries, DynamicTag::RelrSize);
+
+		if let (Some(address), Some(size)) = (address, size) {
+			let data = self
+				.virtual_data(address, size)?
+				.ok_or_else(|| format!("RELR relocations at {address:#x} are not mapped"))?;
+
+			return self.read_relr(data);
+		}
+
+		let mut addresses = Vec::new();
+		for section in self.sections.iter().filter(|it| it.kind == SectionType::Relr) {
+			addresses.append(&mut self.read_relr(self.section_data(section)?)?);
+		}
+
+		Ok(addresses)
+	}
+
+	// applies the relative relocations the way a loader mapping the file at `base` would, the ones
+	// that need symbol lookup are left alone and reported in `warnings`
+	pub fn relocate(&mut self, base: u64) -> Result<()> {
+		let (none, relative) = match self.header.machine {
+			Machine::Amd64 => (R_X86_64_NONE, R_X86_64_RELATIVE),
+			Machine::Arm64 => (R_AARCH64_NONE, R_AARCH64_RELATIVE),
+			machine => return Err(format!("Relocating {machine:?} files is not supported").into()),
+		};
+
+		let mut patches = Vec::new();
+
+		for (relocation, _) in self.dynamic_relocations()? {
+			if relocation.kind == relative {
+				patches.push((relocation.offset, base.wrapping_add_signed(relocation.addend)));
+			} else if relocation.kind != none {
+				self.warnings.push(Warning::UnsupportedRelocation {
+					offset: relocation.offset,
+					kind: relocation.kind,
+				});
+			}
+		}
+
+		// RELR addends are implicit, they're whatever the relocated word already holds
+		for address in self.relr_relocations()? {
+			let data = self.read_virtual(address, RELR_WORD_SIZE as usize)?;
+			let addend = self.reader_for(&data).read_u64()?;
+			patches.push((address, base.wrapping_add(addend)));
+		}
+
+		for (address, value) in patches {
+			self.patch_virtual(address, value)?;
+		}
+
+		Ok(())
+	}
+
+	fn patch_virtual(&mut self, address: u64, value: u64) -> Result<()> {
+		let bytes = if self.header.ident.is_little_endian {
+			value.to_le_bytes()
+		} else {
+			value.to_be_bytes()
+		};
+		let size = bytes.len() as u64;
+		let contains = |start: u64, length: u64| {
+			address >= start && (address - start).checked_add(size) <= Some(length)
+		};
+
+		let index = self
+			.segments
+			.iter()
+			.position(|it| it.is_loadable() && contains(it.virtual_address, it.file_size))
+			.ok_or_else(|| {
+				format!("Relocation at {address:#x} is not in the file data of any segment")
+			})?;
+		self.load_segment(index)?;
+
+		self.symbols.take();
+		let segment = &mut self.segments[index];
+		let start = (address - segment.virtual_address) as usize;
+		if let Some(data) = segment.data.get_mut() {
+			data[start..start + bytes.len()].copy_from_slice(&bytes);
+		}
+
+		// loaded sections hold their own copy of the bytes, so keep them in sync. the others are
+		// read from the segment now
+		let sections = self.sections.iter_mut().filter(|it| {
+			it.is_allocated() && it.kind != SectionType::NoBits && contains(it.address, it.size)
+		});
+		for section in sections {
+			let start = (address - section.address) as usize;
+			if let Some(data) = section.data.get_mut() {
+				data[start..start + bytes.len()].copy_from_slice(&bytes);
+			}
+		}
+
+		Ok(())
+	}
+
+	fn bound_relocations(
+		&self,
+		entries: &[DynamicEntry],
+		address_tag: DynamicTag,
+		size_tag: DynamicTag,
+		section_name: &str,
+	) -> Result<Vec<(Rela, Option<String>)>> {
+		let address = find_dynamic_value(entries, address_tag);
+		let size = find_dynamic_value(entries, size_tag);
+
+		// prefer the dynamic entries, which survive stripping of the section headers
+		if let (Some(address), Some(size)) = (address, size) {
+			let data = self
+				.virtual_data(address, size)?
+				.ok_or_else(|| format!("Relocations at {address:#x} are not mapped"))?;
+
+			return self
+				.read_rela(data)?
+				.into_iter()
+				.map(|rela| {
+					let name = match rela.symbol_index {
+						0 => None,
+						index => Some(self.dynamic_symbol(entries, index)?.name),
+					};
+					Ok((rela, name))
+				})
+				.collect();
+		}
+
+		let Some(section) = self.section_by_name(section_name)? else {
+			return Ok(Vec::new());
+		};
+
+		let symbols = match self.sections.get(section.link as usize) {
+			Some(it) if it.kind != SectionType::Null => self.section_symbols(it)?,
+			_ => Vec::new(),
+		};
+
+		self.read_rela(self.section_data(section)?)?
+			.into_iter()
+			.map(|rela| {
+				let name = match rela.symbol_index {
+					0 => None,
+					index => {
+						Some(symbols.get(index as usize).map(|it| it.name.clone()).ok_or_else(
+							|| format!("Relocation refers to missing symbol {index}"),
+						)?)
+					}
+				};
+				Ok((rela, name))
+			})
+			.collect()
+	}
+
+	fn read_rela(&self, data: &[u8]) -> Result<Vec<Rela>> {
+		let mut relocations = Vec::new();
+		let mut reader = self.reader_for(data);
+
+		while reader.remaining() as u64 >= RELA_ENTRY_SIZE {
+			let offset = reader.read_u64()?;
+			let info = reader.read_u64()?;
+			let addend = reader.read_i64()?;
+
+			relocations.push(Rela {
+				offset,
+				kind: info as u32,
+				symbol_index: (info >> 32) as u32,
+				addend,
+			});
+		}
+
+		Ok(relocations)
+	}
+
+	// read into the same shape as `Rela`, with a zero addend
+	fn read_rel(&self, data: &[u8]) -> Result<Vec<Rela>> {
+		let mut relocations = Vec::new();
+		let mut reader = self.reader_for(data);
+
+		while reader.remaining() as u64 >= REL_ENTRY_SIZE {
+			let offset = reader.read_u64()?;
+			let info = reader.read_u64()?;
+
+			relocations.push(Rela {
+				offset,
+				kind: info as u32,
+				symbol_index: (info >> 32) as u32,
+				addend: 0,
+			});
+		}
+
+		Ok(relocations)
+	}
+
+	// an even entry is an address to relocate, an odd entry is a bitmap of which of the next 63
+	// words after the last relocated word also need relocating
+	fn read_relr(&self, data: &[u8]) -> Result<Vec<u64>> {
+		let mut addresses = Vec::new();
+		let mut reader = self.reader_for(data);
+		let mut next = 0;
+
+		while reader.remaining() >= RELR_WORD_SIZE as usize {
+			let entry = reader.read_u64()?;
+
+			if entry & 1 == 0 {
+				addresses.push(entry);
+				next = entry + RELR_WORD_SIZE;
+			} else {
+				let bitmap = entry >> 1;
+				for bit in 0..63 {
+					if bitmap & (1 << bit) != 0 {
+						addresses.push(next + bit * RELR_WORD_SIZE);
+					}
+				}
+				next += 63 * RELR_WORD_SIZE;
+			}
+		}
+
+		Ok(addresses)
+	}
+}
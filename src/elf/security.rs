@@ -0,0 +1,144 @@
+use super::{Elf, FileType, ProgramType, Result};
+use crate::consts::PF_X;
+
+#[derive(Debug, PartialEq)]
+pub enum Relro {
+	None,
+	Partial,
+	Full,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SecurityFeatures {
+	pub nx: bool,
+	pub pie: bool,
+	pub relro: Relro,
+	pub canary: bool,
+	pub fortify: bool,
+	pub rpath: Vec<String>,
+	pub runpath: Vec<String>,
+}
+
+impl Elf {
+	pub fn checksec(&self) -> Result<SecurityFeatures> {
+		Ok(SecurityFeatures {
+			nx: self.has_nx_stack(),
+			pie: self.is_pie()?,
+			relro: self.relro(),
+			canary: self.has_stack_canary()?,
+			fortify: self.is_fortified()?,
+			rpath: self.rpath()?,
+			runpath: self.runpath()?,
+		})
+	}
+
+	// without a `PT_GNU_STACK` the kernel falls back to an executable stack
+	pub fn has_nx_stack(&self) -> bool {
+		self.segments_of_type(ProgramType::GnuStack).any(|it| it.flags & PF_X == 0)
+	}
+
+	// shared libraries are `Dynamic` too, so only count those that can be run on their own
+	pub fn is_pie(&self) -> Result<bool> {
+		if self.header.kind != FileType::Dynamic {
+			return Ok(false);
+		}
+
+		Ok(self.dynamic_flags()?.pie || self.interpreter()?.is_some())
+	}
+
+	pub fn is_fortified(&self) -> Result<bool> {
+		Ok(self.symbols()?.iter().any(|it| {
+			let name = it.name.split('@').next().unwrap_or_default();
+			name.starts_with("__") && name.ends_with("_chk") && name != "__stack_chk_fail"
+		}))
+	}
+
+	// partial RELRO only protects what the linker itself resolves, full RELRO additionally needs
+	// everything bound at load time so the GOT can be made read-only too
+	pub fn relro(&self) -> Relro {
+		if self.segments_of_type(ProgramType::GnuRelro).next().is_none() {
+			return Relro::None;
+		}
+
+		match self.dynamic_flags() {
+			Ok(flags) if flags.bind_now || flags.now => Relro::Full,
+			_ => Relro::Partial,
+		}
+	}
+
+	pub fn has_stack_canary(&self) -> Result<bool> {
+		Ok(self.symbols()?.iter().any(|it| {
+			// `.symtab` names can carry the version, e.g. `__stack_chk_fail@GLIBC_2.4`
+			let name = it.name.split('@').next().unwrap_or_default();
+			name == "__stack_chk_fail" || name == "__stack_chk_guard"
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::testing::fixture;
+
+	#[test]
+	fn relro_matches_linker_options() {
+		assert_eq!(fixture("sample").relro(), Relro::Full);
+		assert_eq!(fixture("sample-partial-relro").relro(), Relro::Partial);
+		assert_eq!(fixture("sample-no-relro").relro(), Relro::None);
+	}
+
+	#[test]
+	fn checksec_reports_every_feature() {
+		assert_eq!(
+			fixture("sample").checksec().unwrap(),
+			SecurityFeatures {
+				nx: true,
+				pie: true,
+				relro: Relro::Full,
+				canary: false,
+				fortify: false,
+				rpath: vec![],
+				runpath: vec![],
+			}
+		);
+		// also linked with `-z execstack` and an old-style `DT_RPATH`
+		assert_eq!(
+			fixture("sample-no-relro").checksec().unwrap(),
+			SecurityFeatures {
+				nx: false,
+				pie: true,
+				relro: Relro::None,
+				canary: false,
+				fortify: false,
+				rpath: vec!["/opt/legacy".to_string()],
+				runpath: vec![],
+			}
+		);
+		assert_eq!(
+			fixture("hardened").checksec().unwrap(),
+			SecurityFeatures {
+				nx: true,
+				pie: true,
+				relro: Relro::Full,
+				canary: true,
+				fortify: true,
+				rpath: vec![],
+				runpath: vec!["$ORIGIN/lib".to_string(), "/opt/$LIB".to_string()],
+			}
+		);
+	}
+
+	#[test]
+	fn objects_have_no_runtime_protections() {
+		let elf = fixture("sample.o");
+
+		// no `PT_GNU_STACK` either, that's only added when linking
+		assert!(!elf.has_nx_stack());
+		assert!(!elf.is_pie().unwrap());
+		assert_eq!(elf.relro(), Relro::None);
+		assert!(!elf.has_stack_canary().unwrap());
+		assert!(!elf.is_fortified().unwrap());
+		assert!(elf.rpath().unwrap().is_empty());
+		assert!(elf.runpath().unwrap().is_empty());
+	}
+}
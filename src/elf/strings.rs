@@ -0,0 +1,126 @@
+use super::{Elf, Result, Section, SectionFlags, SectionType};
+
+#[derive(Debug)]
+pub struct StringHit {
+	pub section: String,
+	pub offset: usize,
+	pub string: String,
+}
+
+impl Elf {
+	// only string tables and sections flagged as holding strings are scanned, so code and data
+	// don't produce garbage hits
+	pub fn find_string(&self, needle: &str) -> Result<Vec<StringHit>> {
+		self.find_string_where(needle, |_| false)
+	}
+
+	// literals in linked files are usually merged into `.rodata`, which isn't flagged as strings
+	pub fn find_string_with_rodata(&self, needle: &str) -> Result<Vec<StringHit>> {
+		let rodata = self.section_by_name(".rodata")?;
+		self.find_string_where(needle, |section| rodata.is_some_and(|it| std::ptr::eq(it, section)))
+	}
+
+	// every string table entry as (section index, offset, string). only strings that follow
+	// a null are listed, though references may also point into the middle of one when the
+	// linker merged a suffix like `bar` into `foobar`. empty strings, including the one every
+	// table starts with, are skipped. like `find_string`, invalid UTF-8 is replaced rather than
+	// failing the whole listing over one odd name
+	pub fn all_strings(&self) -> Result<Vec<(usize, usize, String)>> {
+		let mut strings = Vec::new();
+
+		let tables = self.sections.iter().enumerate();
+		for (index, section) in tables.filter(|(_, it)| it.kind == SectionType::StringTable) {
+			let mut offset = 0;
+			for string in self.section_data(section)?.split(|&it| it == 0) {
+				if !string.is_empty() {
+					strings.push((index, offset, String::from_utf8_lossy(string).into_owned()));
+				}
+				offset += string.len() + 1;
+			}
+		}
+
+		Ok(strings)
+	}
+
+	fn find_string_where(
+		&self,
+		needle: &str,
+		include: impl Fn(&Section) -> bool,
+	) -> Result<Vec<StringHit>> {
+		let mut hits = Vec::new();
+		// every string would contain it, including the empty ones between padding
+		if needle.is_empty() {
+			return Ok(hits);
+		}
+
+		let sections = self.sections.iter().filter(|it| {
+			it.kind == SectionType::StringTable
+				|| it.kind != SectionType::NoBits
+					&& it.section_flags().contains(SectionFlags::STRINGS)
+				|| include(it)
+		});
+		for section in sections {
+			let mut offset = 0;
+			for string in self.section_data(section)?.split(|&it| it == 0) {
+				if string.windows(needle.len()).any(|it| it == needle.as_bytes()) {
+					hits.push(StringHit {
+						section: self.section_name(section)?.to_owned(),
+						offset,
+						string: String::from_utf8_lossy(string).into_owned(),
+					});
+				}
+				offset += string.len() + 1;
+			}
+		}
+
+		Ok(hits)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		elf::Elf,
+		testing::{fixture, fixture_bytes},
+	};
+
+	#[test]
+	fn empty_needle_finds_nothing() {
+		let elf = fixture("sample");
+
+		assert!(elf.find_string("").unwrap().is_empty());
+		assert!(elf.find_string_with_rodata("").unwrap().is_empty());
+	}
+
+	#[test]
+	fn needles_are_found_by_substring() {
+		let elf = fixture("sample");
+
+		let hits = elf.find_string_with_rodata("sample fixture").unwrap();
+		assert_eq!(hits.len(), 1);
+		assert_eq!(hits[0].section, ".rodata");
+		assert_eq!(hits[0].string, "Hello from the sample fixture");
+
+		let hits = elf.find_string("hidden_val").unwrap();
+		assert!(hits.iter().any(|it| it.section == ".strtab" && it.string == "hidden_value"));
+	}
+
+	#[test]
+	fn invalid_utf8_is_replaced_the_same_way() {
+		let elf = fixture("sample");
+		let (index, offset, _) =
+			elf.all_strings().unwrap().into_iter().find(|(_, _, it)| it == "counter").unwrap();
+
+		let mut bytes = fixture_bytes("sample");
+		let table = elf.sections()[index].offset as usize;
+		bytes[table + offset] = 0xFF;
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		let strings = elf.all_strings().unwrap();
+		assert!(strings.contains(&(index, offset, "\u{FFFD}ounter".to_string())));
+
+		let hits = elf.find_string("ounter").unwrap();
+		let hit = hits.iter().find(|it| it.section == ".strtab" && it.offset == offset).unwrap();
+		assert_eq!(hit.string, "\u{FFFD}ounter");
+	}
+}
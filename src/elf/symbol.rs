@@ -0,0 +1,700 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use super::{
+	dynamic::{find_dynamic_value, DynamicEntry, DynamicTag},
+	read_string, Elf, FileType, Result, Section, SectionFlags, SectionType,
+};
+use crate::{
+	consts::{SHN_ABS, SHN_COMMON, SHN_LORESERVE, SHN_UNDEF, STB_GNU_UNIQUE, STT_GNU_IFUNC},
+	demangle::demangle,
+	error::Error,
+	reader::Reader,
+};
+
+const SYMBOL_ENTRY_SIZE: u64 = 24;
+
+#[derive(Clone)]
+pub struct Symbol {
+	pub name: String,
+	pub binding: SymbolBinding,
+	pub kind: SymbolType,
+	pub other: u8,
+	pub section_index: u16,
+	pub value: u64,
+	pub size: u64,
+}
+
+impl Symbol {
+	// can be overridden by a global definition of the same name
+	pub fn is_weak(&self) -> bool {
+		self.binding == SymbolBinding::Weak
+	}
+
+	// the value is a plain number, not an address that moves when relocated
+	pub fn is_absolute(&self) -> bool {
+		self.section_index == SHN_ABS
+	}
+
+	// only the lowest two bits of `other` are defined
+	pub fn visibility(&self) -> SymbolVisibility {
+		SymbolVisibility::from(self.other & 0x3)
+	}
+
+	// not allocated yet, the linker reserves `size` bytes aligned to `value`
+	pub fn is_common(&self) -> bool {
+		self.section_index == SHN_COMMON
+	}
+
+	pub fn section_name<'a>(&self, elf: &'a Elf) -> Result<Option<&'a str>> {
+		match self.section_index {
+			SHN_UNDEF => Ok(Some("*UND*")),
+			SHN_ABS => Ok(Some("*ABS*")),
+			SHN_COMMON => Ok(Some("*COM*")),
+			// also covers `SHN_XINDEX`, whose real index is only found in `.symtab_shndx`
+			SHN_LORESERVE.. => Ok(None),
+			index => {
+				let section = elf.sections.get(index as usize).ok_or_else(|| {
+					format!("Symbol {} refers to missing section {index}", self.name)
+				})?;
+
+				elf.section_name(section).map(Some)
+			}
+		}
+	}
+
+	// in relocatable files the value is relative to the symbol's section, elsewhere it's an address.
+	// `None` for symbols without bytes in the file, like undefined ones or those in bss
+	pub fn file_offset(&self, elf: &Elf) -> Result<Option<u64>> {
+		if self.section_index == SHN_UNDEF || self.section_index >= SHN_LORESERVE {
+			return Ok(None);
+		}
+
+		if elf.header.kind != FileType::Relocatable {
+			return Ok(elf.virtual_to_offset(self.value));
+		}
+
+		let index = self.section_index;
+		let section = elf
+			.sections
+			.get(index as usize)
+			.ok_or_else(|| format!("Symbol {} refers to missing section {index}", self.name))?;
+		if section.kind == SectionType::NoBits {
+			return Ok(None);
+		}
+
+		Ok(Some(section.offset + self.value))
+	}
+
+	// undefined symbols have no address, so `nm` leaves that column blank. `None` for the symbols
+	// `nm` only lists with `--debug-syms`, like file and section symbols
+	pub fn nm_format(&self, elf: &Elf) -> Result<Option<String>> {
+		if self.name.is_empty() || matches!(self.kind, SymbolType::File | SymbolType::Section) {
+			return Ok(None);
+		}
+
+		let letter = self.nm_letter(elf)?;
+		let name = demangle(&self.name);
+		if matches!(letter, 'U' | 'w' | 'v') {
+			Ok(Some(format!("{:16} {letter} {name}", "")))
+		} else {
+			Ok(Some(format!("{:016x} {letter} {name}", self.value)))
+		}
+	}
+
+	// the letters `nm` uses, lowercase for local symbols
+	fn nm_letter(&self, elf: &Elf) -> Result<char> {
+		let is_object = self.kind == SymbolType::Object;
+
+		if self.is_weak() {
+			return Ok(match (self.section_index == SHN_UNDEF, is_object) {
+				(true, true) => 'v',
+				(true, false) => 'w',
+				(false, true) => 'V',
+				(false, false) => 'W',
+			});
+		}
+		if self.kind == SymbolType::OperatingSystem(STT_GNU_IFUNC) {
+			return Ok('i');
+		}
+		if self.binding == SymbolBinding::OperatingSystem(STB_GNU_UNIQUE) {
+			return Ok('u');
+		}
+
+		let letter = match self.section_index {
+			SHN_UNDEF => return Ok('U'),
+			SHN_COMMON => return Ok('C'),
+			SHN_ABS => 'a',
+			SHN_LORESERVE.. => '?',
+			index => {
+				let section = elf.sections.get(index as usize).ok_or_else(|| {
+					format!("Symbol {} refers to missing section {index}", self.name)
+				})?;
+				let flags = section.section_flags();
+
+				if !section.is_allocated() && elf.section_name(section)?.starts_with(".debug") {
+					'N'
+				} else if !section.is_allocated() {
+					'n'
+				} else if section.kind == SectionType::NoBits {
+					'b'
+				} else if flags.contains(SectionFlags::EXECUTE) {
+					't'
+				} else if flags.contains(SectionFlags::WRITE) {
+					'd'
+				} else {
+					'r'
+				}
+			}
+		};
+
+		Ok(match self.binding {
+			SymbolBinding::Local => letter,
+			_ => letter.to_ascii_uppercase(),
+		})
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct SymbolStats {
+	pub counts: HashMap<(SymbolBinding, SymbolType), usize>,
+}
+
+impl SymbolStats {
+	pub fn count(&self, binding: SymbolBinding, kind: SymbolType) -> usize {
+		self.counts.get(&(binding, kind)).copied().unwrap_or_default()
+	}
+
+	pub fn total(&self) -> usize {
+		self.counts.values().sum()
+	}
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SymbolBinding {
+	Local,
+	Global,
+	Weak,
+	OperatingSystem(u8),
+	Processor(u8),
+	Other(u8),
+}
+
+impl SymbolBinding {
+	pub fn all_known() -> &'static [Self] {
+		&[Self::Local, Self::Global, Self::Weak]
+	}
+}
+
+impl From<u8> for SymbolBinding {
+	fn from(value: u8) -> Self {
+		match value {
+			0x0 => Self::Local,
+			0x1 => Self::Global,
+			0x2 => Self::Weak,
+			0x3..=0x9 => Self::Other(value),
+			0xA..=0xC => Self::OperatingSystem(value),
+			0xD..=0xF => Self::Processor(value),
+			_ => Self::Other(value),
+		}
+	}
+}
+
+impl From<SymbolBinding> for u8 {
+	fn from(binding: SymbolBinding) -> Self {
+		match binding {
+			SymbolBinding::Local => 0x0,
+			SymbolBinding::Global => 0x1,
+			SymbolBinding::Weak => 0x2,
+			SymbolBinding::OperatingSystem(value) => value,
+			SymbolBinding::Processor(value) => value,
+			SymbolBinding::Other(value) => value,
+		}
+	}
+}
+
+// hidden and internal symbols aren't visible outside their component, even if they're global
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SymbolVisibility {
+	Default,
+	Internal,
+	Hidden,
+	Protected,
+	Other(u8),
+}
+
+impl SymbolVisibility {
+	pub fn all_known() -> &'static [Self] {
+		&[Self::Default, Self::Internal, Self::Hidden, Self::Protected]
+	}
+}
+
+impl From<u8> for SymbolVisibility {
+	fn from(value: u8) -> Self {
+		match value {
+			0x0 => Self::Default,
+			0x1 => Self::Internal,
+			0x2 => Self::Hidden,
+			0x3 => Self::Protected,
+			_ => Self::Other(value),
+		}
+	}
+}
+
+impl From<SymbolVisibility> for u8 {
+	fn from(visibility: SymbolVisibility) -> Self {
+		match visibility {
+			SymbolVisibility::Default => 0x0,
+			SymbolVisibility::Internal => 0x1,
+			SymbolVisibility::Hidden => 0x2,
+			SymbolVisibility::Protected => 0x3,
+			SymbolVisibility::Other(value) => value,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SymbolType {
+	NoType,
+	Object,
+	Function,
+	Section,
+	File,
+	Common,
+	ThreadLocalStorage,
+	OperatingSystem(u8),
+	Processor(u8),
+	Other(u8),
+}
+
+impl SymbolType {
+	pub fn all_known() -> &'static [Self] {
+		&[
+			Self::NoType,
+			Self::Object,
+			Self::Function,
+			Self::Section,
+			Self::File,
+			Self::Common,
+			Self::ThreadLocalStorage,
+		]
+	}
+}
+
+impl From<u8> for SymbolType {
+	fn from(value: u8) -> Self {
+		match value {
+			0x0 => Self::NoType,
+			0x1 => Self::Object,
+			0x2 => Self::Function,
+			0x3 => Self::Section,
+			0x4 => Self::File,
+			0x5 => Self::Common,
+			0x6 => Self::ThreadLocalStorage,
+			0x7..=0x9 => Self::Other(value),
+			0xA..=0xC => Self::OperatingSystem(value),
+			0xD..=0xF => Self::Processor(value),
+			_ => Self::Other(value),
+		}
+	}
+}
+
+impl From<SymbolType> for u8 {
+	fn from(symbol_type: SymbolType) -> Self {
+		match symbol_type {
+			SymbolType::NoType => 0x0,
+			SymbolType::Object => 0x1,
+			SymbolType::Function => 0x2,
+			SymbolType::Section => 0x3,
+			SymbolType::File => 0x4,
+			SymbolType::Common => 0x5,
+			SymbolType::ThreadLocalStorage => 0x6,
+			SymbolType::OperatingSystem(value) => value,
+			SymbolType::Processor(value) => value,
+			SymbolType::Other(value) => value,
+		}
+	}
+}
+
+impl Elf {
+	pub fn symbols(&self) -> Result<Vec<Symbol>> {
+		let mut symbols = Vec::new();
+
+		let symbol_sections = self.sections.iter().filter(|it| {
+			it.kind == SectionType::SymbolTable || it.kind == SectionType::LinkerSymbolTable
+		});
+		for section in symbol_sections {
+			symbols.append(&mut self.section_symbols(section)?);
+		}
+
+		Ok(symbols)
+	}
+
+	// the full `.symtab` view, usually gone from stripped files
+	pub fn static_symbols(&self) -> Result<Vec<Symbol>> {
+		self.symbols_of_kind(SectionType::SymbolTable)
+	}
+
+	// the `.dynsym` view the dynamic linker works with
+	pub fn dynamic_symbols(&self) -> Result<Vec<Symbol>> {
+		self.symbols_of_kind(SectionType::LinkerSymbolTable)
+	}
+
+	// `.llvm_addrsig` lists the symbols whose address is taken, which identical code folding must
+	// not merge
+	pub fn address_significant_symbols(&self) -> Result<Vec<Symbol>> {
+		let mut significant = Vec::new();
+
+		for section in self.sections.iter().filter(|it| it.kind == SectionType::LlvmAddrsig) {
+			let table = self
+				.sections
+				.get(section.link as usize)
+				.filter(|it| it.kind == SectionType::SymbolTable)
+				.ok_or_else(|| {
+					Error::malformed(format!(
+						"Address significance table links to invalid symbol table index {}",
+						section.link
+					))
+				})?;
+			let symbols = self.section_symbols(table)?;
+
+			let mut reader = self.reader_for(self.section_data(section)?);
+			while !reader.is_empty() {
+				let index = reader.read_uleb128()?;
+				let symbol = symbols.get(index as usize).ok_or_else(|| {
+					Error::malformed(format!(
+						"Address significance table refers to missing symbol {index}"
+					))
+				})?;
+				significant.push(symbol.clone());
+			}
+		}
+
+		Ok(significant)
+	}
+
+	// counts `.symtab` if it's there, `.dynsym` otherwise. stripped files just come out empty
+	pub fn symbol_stats(&self) -> Result<SymbolStats> {
+		let mut symbols = self.static_symbols()?;
+		if symbols.is_empty() {
+			symbols = self.dynamic_symbols()?;
+		}
+
+		let mut stats = SymbolStats::default();
+		// the null symbol isn't a real one
+		for symbol in
+			symbols.iter().filter(|it| it.section_index != SHN_UNDEF || !it.name.is_empty())
+		{
+			*stats.counts.entry((symbol.binding, symbol.kind)).or_default() += 1;
+		}
+
+		Ok(stats)
+	}
+
+	fn symbols_of_kind(&self, kind: SectionType) -> Result<Vec<Symbol>> {
+		let mut symbols = Vec::new();
+
+		for section in self.sections.iter().filter(|it| it.kind == kind) {
+			symbols.append(&mut self.section_symbols(section)?);
+		}
+
+		Ok(symbols)
+	}
+
+	// ties are broken by name so symbol maps come out the same every time
+	pub fn symbols_sorted_by_address(&self) -> Result<Vec<Symbol>> {
+		let mut symbols: Vec<_> = self
+			.symbols()?
+			.into_iter()
+			.filter(|it| matches!(it.kind, SymbolType::Function | SymbolType::Object))
+			.filter(|it| it.section_index != SHN_UNDEF)
+			.collect();
+
+		symbols.sort_by(|a, b| a.value.cmp(&b.value).then_with(|| a.name.cmp(&b.name)));
+		Ok(symbols)
+	}
+
+	// symbolizes an address the way a profiler would, by the function or object covering it.
+	// values in relocatable objects are offsets into their own section rather than addresses, so
+	// those never match
+	pub fn symbol_containing(&self, address: u64) -> Result<Option<&Symbol>> {
+		if !matches!(self.header.kind, FileType::Executable | FileType::Dynamic) {
+			return Ok(None);
+		}
+
+		let in_allocated_section = |symbol: &Symbol| {
+			symbol.section_index != SHN_UNDEF
+				&& symbol.section_index < SHN_LORESERVE
+				&& self
+					.sections
+					.get(symbol.section_index as usize)
+					.is_some_and(|it| it.is_allocated())
+		};
+		let mut candidates: Vec<_> = self
+			.cached_symbols()?
+			.iter()
+			.filter(|it| matches!(it.kind, SymbolType::Function | SymbolType::Object))
+			.filter(|it| it.value <= address && in_allocated_section(it))
+			.collect();
+
+		// closest start last, and among symbols starting at the same address the tightest one last
+		candidates.sort_by_key(|it| (it.value, Reverse(it.size)));
+
+		let covering =
+			candidates.iter().rposition(|it| it.size > 0 && address - it.value < it.size);
+		if let Some(index) = covering {
+			return Ok(Some(candidates[index]));
+		}
+
+		// symbols without a size (e.g. from hand-written assembly) are assumed to run up to the
+		// next one
+		Ok(candidates.pop().filter(|it| it.size == 0))
+	}
+
+	fn cached_symbols(&self) -> Result<&[Symbol]> {
+		if let Some(symbols) = self.symbols.get() {
+			return Ok(symbols);
+		}
+
+		let symbols = self.symbols()?;
+		Ok(self.symbols.get_or_init(|| symbols))
+	}
+
+	pub fn section_symbols(&self, section: &Section) -> Result<Vec<Symbol>> {
+		// names would be garbage (or out of bounds) if read from anything but a string table
+		let strings = match self.sections.get(section.link as usize) {
+			Some(it) if it.kind == SectionType::StringTable => self.section_data(it)?,
+			_ => {
+				let index = self
+					.section_index_of(section)
+					.map_or_else(|| "?".to_owned(), |it| it.to_string());
+				return Err(Error::malformed(format!(
+					"Symbol table section {index} links to invalid string table index {}",
+					section.link
+				)));
+			}
+		};
+
+		let mut symbols = Vec::new();
+		let mut reader = self.reader_for(self.section_data(section)?);
+
+		while reader.remaining() as u64 >= SYMBOL_ENTRY_SIZE {
+			symbols.push(read_symbol(&mut reader, strings)?);
+		}
+
+		Ok(symbols)
+	}
+
+	// reads through the `DT_SYMTAB`/`DT_STRTAB` addresses so it works without section headers
+	pub(super) fn dynamic_symbol(&self, entries: &[DynamicEntry], index: u32) -> Result<Symbol> {
+		let table = find_dynamic_value(entries, DynamicTag::SymbolTable)
+			.ok_or("Dynamic section has no symbol table")?;
+		let entry_size =
+			find_dynamic_value(entries, DynamicTag::SymbolEntrySize).unwrap_or(SYMBOL_ENTRY_SIZE);
+
+		let address = table + index as u64 * entry_size;
+		let data = self
+			.virtual_data(address, SYMBOL_ENTRY_SIZE)?
+			.ok_or_else(|| format!("Dynamic symbol {index} at {address:#x} is not mapped"))?;
+		let strings = self.dynamic_string_table(entries)?;
+
+		read_symbol(&mut self.reader_for(data), strings)
+	}
+}
+
+fn read_symbol(reader: &mut Reader, strings: &[u8]) -> Result<Symbol> {
+	let name_index = reader.read_u32()? as usize;
+	let info = reader.read_u8()?;
+	let other = reader.read_u8()?;
+	let section_index = reader.read_u16()?;
+	let value = reader.read_u64()?;
+	let size = reader.read_u64()?;
+
+	Ok(Symbol {
+		name: read_string(strings, name_index)?.to_owned(),
+		binding: SymbolBinding::from(info >> 4),
+		kind: SymbolType::from(info & 0xF),
+		other,
+		section_index,
+		value,
+		size,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		consts::SHN_XINDEX,
+		error::ErrorKind,
+		testing::{fixture, fixture_bytes, section_header_at, write_u32_at},
+	};
+
+	fn symbol(elf: &Elf, name: &str) -> Symbol {
+		elf.symbols().unwrap().into_iter().find(|it| it.name == name).unwrap()
+	}
+
+	#[test]
+	fn symbol_containing_covers_the_whole_range() {
+		let elf = fixture("sample");
+		let add = symbol(&elf, "add");
+		let counter = symbol(&elf, "counter");
+
+		for address in [add.value, add.value + add.size - 1] {
+			assert_eq!(elf.symbol_containing(address).unwrap().unwrap().name, "add");
+		}
+		assert_eq!(elf.symbol_containing(counter.value + 2).unwrap().unwrap().name, "counter");
+	}
+
+	#[test]
+	fn symbol_containing_ignores_relocatable_objects() {
+		let elf = fixture("sample.o");
+
+		assert!(elf.symbol_containing(3).unwrap().is_none());
+	}
+
+	#[test]
+	fn symbol_table_with_bad_string_table_link_is_malformed() {
+		let elf = fixture("sample");
+		let table = elf.sections().iter().position(|it| it.kind == SectionType::SymbolTable);
+		let table = table.unwrap();
+
+		let mut bytes = fixture_bytes("sample");
+		let header = section_header_at(&bytes, table);
+		// `sh_link`
+		write_u32_at(&mut bytes, header + 0x28, 99);
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+
+		let error = elf.static_symbols().err().unwrap();
+		assert_eq!(error.kind(), &ErrorKind::Malformed);
+		assert_eq!(
+			error.to_string(),
+			format!("Symbol table section {table} links to invalid string table index 99")
+		);
+	}
+
+	#[test]
+	fn symbol_enums_round_trip() {
+		for &it in SymbolBinding::all_known() {
+			assert_eq!(SymbolBinding::from(u8::from(it)), it);
+		}
+		for &it in SymbolVisibility::all_known() {
+			assert_eq!(SymbolVisibility::from(u8::from(it)), it);
+		}
+		for &it in SymbolType::all_known() {
+			assert_eq!(SymbolType::from(u8::from(it)), it);
+		}
+		// bindings and types are 4 bits wide and visibilities 2, but the conversions take any byte
+		for value in 0..=u8::MAX {
+			assert_eq!(u8::from(SymbolBinding::from(value)), value);
+			assert_eq!(u8::from(SymbolVisibility::from(value)), value);
+			assert_eq!(u8::from(SymbolType::from(value)), value);
+		}
+	}
+
+	#[test]
+	fn section_names_cover_the_special_indices() {
+		let elf = fixture("sample.o");
+		let section_name = |name| symbol(&elf, name).section_name(&elf).unwrap();
+
+		assert_eq!(section_name("add"), Some(".text"));
+		assert_eq!(section_name("main"), Some(".text.startup"));
+		assert_eq!(section_name("counter"), Some(".data"));
+		assert_eq!(section_name("calls"), Some(".bss"));
+		assert_eq!(section_name("greeting"), Some(".rodata"));
+		assert_eq!(section_name("per_thread"), Some(".tdata"));
+		assert_eq!(section_name("puts"), Some("*UND*"));
+		assert_eq!(section_name("sample.c"), Some("*ABS*"));
+
+		// `-fno-common` is the default, so common and extended indices are made up
+		let mut common = symbol(&elf, "counter");
+		common.section_index = SHN_COMMON;
+		assert_eq!(common.section_name(&elf).unwrap(), Some("*COM*"));
+		common.section_index = SHN_XINDEX;
+		assert_eq!(common.section_name(&elf).unwrap(), None);
+		common.section_index = 99;
+		let error = common.section_name(&elf).unwrap_err();
+		assert_eq!(error.to_string(), "Symbol counter refers to missing section 99");
+	}
+
+	#[test]
+	fn nm_format_matches_nm() {
+		let elf = fixture("sample.o");
+		let mut lines = elf
+			.symbols()
+			.unwrap()
+			.iter()
+			.filter_map(|it| it.nm_format(&elf).unwrap())
+			.collect::<Vec<_>>();
+		lines.sort();
+
+		// `nm fixtures/sample.o`, which leaves out the symbol for sample.c
+		let mut expected = vec![
+			"                 U _GLOBAL_OFFSET_TABLE_",
+			"0000000000000000 T add",
+			"0000000000000000 b calls",
+			"000000000000000c D counter",
+			"0000000000000010 W fallback",
+			"0000000000000000 R greeting",
+			"0000000000000008 D hidden_value",
+			"0000000000000000 D internal_value",
+			"0000000000000000 T main",
+			"0000000000000000 D message",
+			"0000000000000000 D per_thread",
+			"0000000000000004 D protected_value",
+			"                 U puts",
+			"0000000000000010 C scratch",
+			"0000000000000040 A table_size",
+		];
+		expected.sort();
+		assert_eq!(lines, expected);
+	}
+
+	#[test]
+	fn nm_format_demangles_names() {
+		let elf = fixture("sample.o");
+		let mut add = symbol(&elf, "add");
+		add.name = "_ZN9rustsetta3addEii".to_string();
+		assert_eq!(
+			add.nm_format(&elf).unwrap().unwrap(),
+			"0000000000000000 T rustsetta::add(int, int)"
+		);
+	}
+
+	#[test]
+	fn special_symbols_are_recognized() {
+		let elf = fixture("sample.o");
+
+		let fallback = symbol(&elf, "fallback");
+		assert!(fallback.is_weak());
+		assert!(!fallback.is_absolute() && !fallback.is_common());
+
+		let table_size = symbol(&elf, "table_size");
+		assert!(table_size.is_absolute());
+		assert_eq!(table_size.value, 64);
+		assert!(!table_size.is_weak() && !table_size.is_common());
+
+		// the value of a common symbol is its alignment
+		let scratch = symbol(&elf, "scratch");
+		assert!(scratch.is_common());
+		assert_eq!((scratch.value, scratch.size), (16, 16));
+		assert!(!scratch.is_weak() && !scratch.is_absolute());
+
+		let counter = symbol(&elf, "counter");
+		assert!(!counter.is_weak() && !counter.is_absolute() && !counter.is_common());
+	}
+
+	#[test]
+	fn visibility_is_read_from_the_low_bits() {
+		let elf = fixture("sample.o");
+		let visibility = |name| symbol(&elf, name).visibility();
+
+		assert_eq!(visibility("counter"), SymbolVisibility::Default);
+		assert_eq!(visibility("internal_value"), SymbolVisibility::Internal);
+		assert_eq!(visibility("hidden_value"), SymbolVisibility::Hidden);
+		assert_eq!(visibility("protected_value"), SymbolVisibility::Protected);
+
+		let mut hidden = symbol(&elf, "hidden_value");
+		hidden.other |= 0xF0;
+		assert_eq!(hidden.visibility(), SymbolVisibility::Hidden);
+	}
+}
@@ -0,0 +1,48 @@
+use super::{read_string, Elf, Result};
+
+const NT_STAPSDT: u32 = 3;
+
+#[derive(Debug, PartialEq)]
+pub struct UsdtProbe {
+	pub provider: String,
+	pub name: String,
+	// e.g. `-4@%edi 8@%rsi`, size and location of each argument
+	pub arguments: String,
+	pub pc: u64,
+	pub base: u64,
+	// 0 for probes without a semaphore
+	pub semaphore: u64,
+}
+
+impl Elf {
+	// the addresses are the ones recorded at link time. if the file was prelinked, `.stapsdt.base`
+	// has moved by as much as they're off
+	pub fn usdt_probes(&self) -> Result<Vec<UsdtProbe>> {
+		let mut probes = Vec::new();
+
+		let notes = self.notes()?;
+		for note in notes.iter().filter(|it| it.name == "stapsdt" && it.kind == NT_STAPSDT) {
+			let mut reader = self.reader_for(&note.description);
+			let pc = reader.read_u64()?;
+			let base = reader.read_u64()?;
+			let semaphore = reader.read_u64()?;
+
+			// the strings follow the addresses back to back, each with its own terminator
+			let strings = &note.description[reader.position()..];
+			let provider = read_string(strings, 0)?;
+			let name = read_string(strings, provider.len() + 1)?;
+			let arguments = read_string(strings, provider.len() + name.len() + 2)?;
+
+			probes.push(UsdtProbe {
+				provider: provider.to_owned(),
+				name: name.to_owned(),
+				arguments: arguments.to_owned(),
+				pc,
+				base,
+				semaphore,
+			});
+		}
+
+		Ok(probes)
+	}
+}
@@ -0,0 +1,97 @@
+use super::{read_string, Elf, Result, SectionType};
+use crate::consts::SHT_GNU_VERSYM;
+
+// the top bit of a version index marks a non-default version (`name@version` vs `name@@version`)
+pub(super) const VERSION_INDEX_MASK: u16 = 0x7FFF;
+
+pub struct VersionDef {
+	pub version: u16,
+	pub flags: u16,
+	pub index: u16,
+	pub hash: u32,
+	pub name: String,
+	pub parents: Vec<String>,
+}
+
+impl Elf {
+	pub fn version_definitions(&self) -> Result<Vec<VersionDef>> {
+		let mut definitions = Vec::new();
+
+		let sections =
+			self.sections.iter().filter(|it| it.kind == SectionType::GnuVersionDefinitions);
+		for section in sections {
+			let strings = self.sections.get(section.link as usize).ok_or_else(|| {
+				format!("Version definitions link to missing string table {}", section.link)
+			})?;
+			let strings = self.section_data(strings)?;
+
+			let mut reader = self.reader_for(self.section_data(section)?);
+			let mut offset = 0;
+
+			// the records form a linked list through `vd_next`, each with its own list of names
+			// through `vda_next`, the first name being the version itself
+			loop {
+				reader.seek(offset)?;
+				let version = reader.read_u16()?;
+				let flags = reader.read_u16()?;
+				let index = reader.read_u16()?;
+				let name_count = reader.read_u16()?;
+				let hash = reader.read_u32()?;
+				let names_offset = reader.read_u32()? as usize;
+				let next = reader.read_u32()? as usize;
+
+				let mut names = Vec::with_capacity(name_count.into());
+				let mut name_offset = offset + names_offset;
+				for _ in 0..name_count {
+					reader.seek(name_offset)?;
+					let name = reader.read_u32()? as usize;
+					let next = reader.read_u32()? as usize;
+
+					names.push(read_string(strings, name)?.to_string());
+					name_offset += next;
+				}
+
+				if names.is_empty() {
+					return Err(format!("Version definition {index} has no name").into());
+				}
+
+				let name = names.remove(0);
+				definitions.push(VersionDef {
+					version,
+					flags,
+					index,
+					hash,
+					name,
+					parents: names,
+				});
+
+				if next == 0 {
+					break;
+				}
+				offset += next;
+			}
+		}
+
+		Ok(definitions)
+	}
+
+	// one version index per symbol in the symbol table at `table_index`, `None` if the table isn't
+	// versioned
+	pub(super) fn symbol_version_indices(&self, table_index: usize) -> Result<Option<Vec<u16>>> {
+		let Some(section) = self.sections.iter().find(|it| {
+			it.kind == SectionType::OperatingSystem(SHT_GNU_VERSYM)
+				&& it.link as usize == table_index
+		}) else {
+			return Ok(None);
+		};
+
+		let data = self.section_data(section)?;
+		let mut indices = Vec::with_capacity(data.len() / 2);
+		let mut reader = self.reader_for(data);
+		while reader.remaining() >= 2 {
+			indices.push(reader.read_u16()?);
+		}
+
+		Ok(Some(indices))
+	}
+}
@@ -0,0 +1,264 @@
+use std::io::Write;
+
+use super::{Elf, FileHeader, Result, FILE_HEADER_SIZE, PROGRAM_HEADER_SIZE, SECTION_HEADER_SIZE};
+use crate::consts::{ELFCLASS32, ELFCLASS64, ELFDATA2LSB, ELFDATA2MSB};
+
+impl Elf {
+	// lays the file out again from the headers and bodies, bytes nothing refers to are zeroed
+	// bodies that aren't loaded yet are read along the way
+	pub fn write<W: Write>(&self, mut writer: W) -> Result<()> {
+		let header = &self.header;
+		let program_headers = header.program_header_offset as usize;
+		let section_headers = header.section_header_offset as usize;
+
+		let end = self
+			.segments
+			.iter()
+			.map(|it| it.file_range().end as usize)
+			.chain(self.sections.iter().map(|it| it.file_range().end as usize))
+			.chain([
+				header.header_size as usize,
+				program_headers + self.segments.len() * header.program_header_stride(),
+				section_headers + self.sections.len() * header.section_header_stride(),
+			])
+			.max()
+			.unwrap_or_default()
+			.max(FILE_HEADER_SIZE);
+
+		let mut data = vec![0; end];
+
+		// sections are copied last, in case they were edited without a loaded segment to sync
+		for (index, segment) in self.segments.iter().enumerate() {
+			copy_body(&mut data, segment.offset, self.segment_data(segment)?)
+				.ok_or_else(|| format!("Body of segment {index} doesn't fit in the file"))?;
+		}
+		for section in &self.sections {
+			copy_body(&mut data, section.offset, self.section_data(section)?).ok_or_else(|| {
+				let name = self.section_name(section).unwrap_or("<unnamed>");
+				format!("Body of section {name} doesn't fit in the file")
+			})?;
+		}
+
+		data[..FILE_HEADER_SIZE].copy_from_slice(&header.to_bytes());
+
+		let mut encoder = Encoder::new(header.ident.is_little_endian);
+		self.encode_segments(&mut encoder);
+		data[program_headers..program_headers + encoder.data.len()].copy_from_slice(&encoder.data);
+
+		let mut encoder = Encoder::new(header.ident.is_little_endian);
+		self.encode_sections(&mut encoder);
+		data[section_headers..section_headers + encoder.data.len()].copy_from_slice(&encoder.data);
+
+		writer.write_all(&data)?;
+		Ok(())
+	}
+
+	fn encode_segments(&self, encoder: &mut Encoder) {
+		for segment in &self.segments {
+			let start = encoder.data.len();
+			encoder.u32(segment.kind.into());
+			encoder.u32(segment.flags);
+			encoder.u64(segment.offset);
+			encoder.u64(segment.virtual_address);
+			encoder.u64(segment.physical_address);
+			encoder.u64(segment.file_size);
+			encoder.u64(segment.memory_size);
+			encoder.u64(segment.alignment);
+			encoder.data.resize(start + self.header.program_header_stride(), 0);
+		}
+	}
+
+	fn encode_sections(&self, encoder: &mut Encoder) {
+		for section in &self.sections {
+			let start = encoder.data.len();
+			encoder.u32(section.name_index as u32);
+			encoder.u32(section.kind.into());
+			encoder.u64(section.flags);
+			encoder.u64(section.address);
+			encoder.u64(section.offset);
+			encoder.u64(section.size);
+			encoder.u32(section.link);
+			encoder.u32(section.info);
+			encoder.u64(section.address_alignment);
+			encoder.u64(section.entry_size);
+			encoder.data.resize(start + self.header.section_header_stride(), 0);
+		}
+	}
+}
+
+impl FileHeader {
+	// any padding past the fields of an entry is written as zeros
+	pub(super) fn program_header_stride(&self) -> usize {
+		(self.program_header_entry_size as usize).max(PROGRAM_HEADER_SIZE)
+	}
+
+	pub(super) fn section_header_stride(&self) -> usize {
+		(self.section_header_entry_size as usize).max(SECTION_HEADER_SIZE)
+	}
+
+	// the ident padding is written back as parsed, including anything hidden in it
+	pub fn to_bytes(&self) -> [u8; FILE_HEADER_SIZE] {
+		let ident = &self.ident;
+		let class = if ident.is_64_bit {
+			ELFCLASS64
+		} else {
+			ELFCLASS32
+		};
+		let data = if ident.is_little_endian {
+			ELFDATA2LSB
+		} else {
+			ELFDATA2MSB
+		};
+
+		let mut encoder = Encoder::new(ident.is_little_endian);
+		encoder.data.extend_from_slice(&ident.magic.to_le_bytes());
+		encoder.u8(class);
+		encoder.u8(data);
+		encoder.u8(ident.version);
+		encoder.u8(ident.os_abi.into());
+		encoder.u8(ident.abi_version);
+		encoder.data.extend_from_slice(&ident.padding);
+
+		encoder.u16(self.kind.into());
+		encoder.u16(self.machine.into());
+		encoder.u32(self.version);
+		encoder.u64(self.entry);
+		encoder.u64(self.program_header_offset);
+		encoder.u64(self.section_header_offset);
+		encoder.u32(self.flags);
+		encoder.u16(self.header_size);
+		encoder.u16(self.program_header_entry_size);
+		encoder.u16(self.program_header_count);
+		encoder.u16(self.section_header_entry_size);
+		encoder.u16(self.section_header_count);
+		encoder.u16(self.section_header_names_index);
+
+		let mut bytes = [0; FILE_HEADER_SIZE];
+		bytes.copy_from_slice(&encoder.data);
+		bytes
+	}
+}
+
+// a body longer than its header says, e.g. after editing `size`, would run past the end
+fn copy_body(data: &mut [u8], offset: u64, body: &[u8]) -> Option<()> {
+	let start = usize::try_from(offset).ok()?;
+	data.get_mut(start..start.checked_add(body.len())?)?.copy_from_slice(body);
+	Some(())
+}
+
+struct Encoder {
+	data: Vec<u8>,
+	is_little_endian: bool,
+}
+
+impl Encoder {
+	fn new(is_little_endian: bool) -> Self {
+		Encoder {
+			data: Vec::new(),
+			is_little_endian,
+		}
+	}
+
+	fn u8(&mut self, value: u8) {
+		self.data.push(value);
+	}
+
+	fn u16(&mut self, value: u16) {
+		let bytes = if self.is_little_endian {
+			value.to_le_bytes()
+		} else {
+			value.to_be_bytes()
+		};
+		self.data.extend_from_slice(&bytes);
+	}
+
+	fn u32(&mut self, value: u32) {
+		let bytes = if self.is_little_endian {
+			value.to_le_bytes()
+		} else {
+			value.to_be_bytes()
+		};
+		self.data.extend_from_slice(&bytes);
+	}
+
+	fn u64(&mut self, value: u64) {
+		let bytes = if self.is_little_endian {
+			value.to_le_bytes()
+		} else {
+			value.to_be_bytes()
+		};
+		self.data.extend_from_slice(&bytes);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Cursor;
+
+	use super::*;
+	use crate::{
+		elf::{ParseOptions, Parser},
+		error::ErrorKind,
+		testing::{fixture, fixture_bytes},
+	};
+
+	fn written(elf: &Elf) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		elf.write(&mut bytes).unwrap();
+		bytes
+	}
+
+	#[test]
+	fn parse_write_parse_is_byte_identical() {
+		let first = written(&fixture("sample"));
+		let second = written(&Elf::try_from(first.as_slice()).unwrap());
+
+		assert_eq!(first, second);
+		assert_eq!(first, fixture_bytes("sample"));
+	}
+
+	#[test]
+	fn ident_padding_is_kept() {
+		let mut bytes = fixture_bytes("sample");
+		bytes[9..16].copy_from_slice(b"hidden!");
+
+		let elf = Elf::try_from(bytes.as_slice()).unwrap();
+		assert_eq!(&elf.header.ident.padding, b"hidden!");
+		assert_eq!(elf.header.to_bytes()[..], bytes[..FILE_HEADER_SIZE]);
+		assert_eq!(written(&elf), bytes);
+
+		let options = ParseOptions {
+			strict_ident_padding: true,
+			..ParseOptions::default()
+		};
+		let error = Parser::with_options(options).parse_reader(Cursor::new(bytes)).err().unwrap();
+		assert_eq!(error.kind(), &ErrorKind::Malformed);
+	}
+
+	#[test]
+	fn header_is_written_without_tables() {
+		let mut elf = fixture("sample");
+		elf.segments.clear();
+		elf.sections.clear();
+		elf.header.header_size = 0;
+		elf.header.program_header_offset = 0;
+		elf.header.section_header_offset = 0;
+
+		assert_eq!(written(&elf), elf.header.to_bytes());
+	}
+
+	#[test]
+	fn bodies_past_the_end_are_an_error() {
+		let mut elf = fixture("sample");
+		elf.load_sections().unwrap();
+		let end = fixture_bytes("sample").len() as u64;
+
+		let index = elf.sections.iter().position(|it| elf.section_name(it).unwrap() == ".comment");
+		let comment = &mut elf.sections[index.unwrap()];
+		comment.offset = end - 1;
+		comment.size = 0;
+
+		let error = elf.write(Vec::new()).err().unwrap();
+		assert_eq!(error.to_string(), "Body of section .comment doesn't fit in the file");
+	}
+}
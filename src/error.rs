@@ -1,9 +1,58 @@
-use std::{fmt, io};
+use std::{fmt, io, str::Utf8Error, string::FromUtf8Error};
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+	Io,
+	Utf8,
+	Malformed,
+	// a read past the end of the file, `available` is what was left from `offset`
+	UnexpectedEof {
+		offset: u64,
+		wanted: u64,
+		available: u64,
+	},
+	Cancelled,
+	Unsupported,
+	// a body that has to be read first, with `Elf::section_data` or `Elf::load_section`
+	NotLoaded,
+	Other,
+}
 
 pub struct Error {
+	kind: ErrorKind,
 	message: String,
 }
 
+impl Error {
+	pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+		Error {
+			kind,
+			message: message.into(),
+		}
+	}
+
+	pub fn malformed(message: impl Into<String>) -> Self {
+		Error::new(ErrorKind::Malformed, message)
+	}
+
+	pub fn unexpected_eof(offset: u64, wanted: u64, available: u64) -> Self {
+		Error::new(
+			ErrorKind::UnexpectedEof {
+				offset,
+				wanted,
+				available,
+			},
+			format!(
+				"Unexpected end of file reading {wanted} bytes at {offset:#x}, only {available} left"
+			),
+		)
+	}
+
+	pub fn kind(&self) -> &ErrorKind {
+		&self.kind
+	}
+}
+
 impl fmt::Display for Error {
 	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(fmt, "{}", self.message)
@@ -18,18 +67,30 @@ impl fmt::Debug for Error {
 
 impl From<io::Error> for Error {
 	fn from(value: io::Error) -> Self {
-		Error { message: value.to_string() }
+		Error::new(ErrorKind::Io, value.to_string())
+	}
+}
+
+impl From<Utf8Error> for Error {
+	fn from(value: Utf8Error) -> Self {
+		Error::new(ErrorKind::Utf8, value.to_string())
+	}
+}
+
+impl From<FromUtf8Error> for Error {
+	fn from(value: FromUtf8Error) -> Self {
+		Error::new(ErrorKind::Utf8, value.to_string())
 	}
 }
 
 impl From<&str> for Error {
 	fn from(value: &str) -> Self {
-		Error { message: value.into() }
+		Error::new(ErrorKind::Other, value)
 	}
 }
 
 impl From<String> for Error {
 	fn from(value: String) -> Self {
-		Error { message: value }
+		Error::new(ErrorKind::Other, value)
 	}
 }
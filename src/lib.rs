@@ -0,0 +1,11 @@
+pub mod consts;
+pub mod demangle;
+pub mod elf;
+pub mod error;
+pub mod reader;
+
+#[cfg(feature = "hash")]
+pub mod sha256;
+
+#[cfg(test)]
+mod testing;
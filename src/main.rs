@@ -1,10 +1,6 @@
-mod elf;
-mod error;
-
 use std::fs::File;
 
-use elf::Elf;
-use error::Error;
+use rustsetta::{elf::Elf, error::Error};
 
 pub fn main() -> Result<(), Error> {
 	let mut args = std::env::args().skip(1);
@@ -14,16 +10,13 @@ pub fn main() -> Result<(), Error> {
 
 	println!("{:?} {:?}", elf.header.ident.os_abi, elf.header.machine);
 
-	let names_index = elf.header.section_header_names_index;
-	let name_data = &elf.sections[names_index as usize].data;
-
-	for section in elf.sections.iter() {
+	for section in elf.sections() {
 		let start = section.name_index;
-		let end = name_data[start..].iter().position(|it| *it == 0).unwrap() + start;
-		let name = std::str::from_utf8(&name_data[start..end])
-			.map_err(|_| "Failed to parse UTF8 string")?;
-
-		println!("{name}: {start}, {end}");
+		// files without a usable name table still have sections worth listing
+		match elf.section_name(section) {
+			Ok(name) => println!("{name}: {start}, {}", start + name.len()),
+			Err(_) => println!("?: {start}"),
+		}
 	}
 
 	Ok(())
@@ -0,0 +1,223 @@
+use crate::error::Error;
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub struct Reader<'a> {
+	data: &'a [u8],
+	position: usize,
+	is_little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+	pub fn new(data: &'a [u8], is_little_endian: bool) -> Self {
+		Reader {
+			data,
+			position: 0,
+			is_little_endian,
+		}
+	}
+
+	pub fn position(&self) -> usize {
+		self.position
+	}
+
+	pub fn remaining(&self) -> usize {
+		self.data.len() - self.position
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.remaining() == 0
+	}
+
+	pub fn seek(&mut self, position: usize) -> Result<()> {
+		if position > self.data.len() {
+			return Err(format!("Offset {position} is past the end of the data").into());
+		}
+		self.position = position;
+		Ok(())
+	}
+
+	pub fn skip(&mut self, count: usize) -> Result<()> {
+		self.read_bytes(count)?;
+		Ok(())
+	}
+
+	pub fn align(&mut self, alignment: usize) -> Result<()> {
+		if alignment > 1 {
+			let padding = (alignment - self.position % alignment) % alignment;
+			self.skip(padding.min(self.remaining()))?;
+		}
+		Ok(())
+	}
+
+	pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8]> {
+		if count > self.remaining() {
+			return Err(format!(
+				"Unexpected end of data reading {count} bytes at offset {}",
+				self.position
+			)
+			.into());
+		}
+
+		let bytes = &self.data[self.position..self.position + count];
+		self.position += count;
+		Ok(bytes)
+	}
+
+	// a u64 takes at most 10 bytes of 7 bits each, anything longer or wider can't be represented
+	pub fn read_uleb128(&mut self) -> Result<u64> {
+		let start = self.position;
+		let mut value = 0;
+
+		for shift in (0..64).step_by(7) {
+			let byte = self.read_u8()?;
+			if shift == 63 && byte > 1 {
+				break;
+			}
+
+			value |= u64::from(byte & 0x7F) << shift;
+			if byte & 0x80 == 0 {
+				return Ok(value);
+			}
+		}
+
+		Err(Error::malformed(format!("ULEB128 at offset {start} overflows 64 bits")))
+	}
+
+	pub fn read_sleb128(&mut self) -> Result<i64> {
+		let start = self.position;
+		let mut value = 0;
+
+		for shift in (0..64).step_by(7) {
+			let byte = self.read_u8()?;
+
+			// the last byte only holds the sign bit, so it has to be all zeros or all ones
+			if shift == 63 && byte != 0 && byte != 0x7F {
+				break;
+			}
+
+			value |= i64::from(byte & 0x7F) << shift;
+			if byte & 0x80 == 0 {
+				// sign extend from the last bit read
+				if shift < 57 && byte & 0x40 != 0 {
+					value |= -1 << (shift + 7);
+				}
+				return Ok(value);
+			}
+		}
+
+		Err(Error::malformed(format!("SLEB128 at offset {start} overflows 64 bits")))
+	}
+
+	pub fn read_u8(&mut self) -> Result<u8> {
+		Ok(self.read_bytes(1)?[0])
+	}
+
+	pub fn read_u16(&mut self) -> Result<u16> {
+		let buffer = self.read_array()?;
+		if self.is_little_endian {
+			Ok(u16::from_le_bytes(buffer))
+		} else {
+			Ok(u16::from_be_bytes(buffer))
+		}
+	}
+
+	pub fn read_u32(&mut self) -> Result<u32> {
+		let buffer = self.read_array()?;
+		if self.is_little_endian {
+			Ok(u32::from_le_bytes(buffer))
+		} else {
+			Ok(u32::from_be_bytes(buffer))
+		}
+	}
+
+	pub fn read_u64(&mut self) -> Result<u64> {
+		let buffer = self.read_array()?;
+		if self.is_little_endian {
+			Ok(u64::from_le_bytes(buffer))
+		} else {
+			Ok(u64::from_be_bytes(buffer))
+		}
+	}
+
+	pub fn read_i64(&mut self) -> Result<i64> {
+		self.read_u64().map(|it| it as i64)
+	}
+
+	fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+		let mut buffer = [0; N];
+		buffer.copy_from_slice(self.read_bytes(N)?);
+		Ok(buffer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::ErrorKind;
+
+	fn uleb128(bytes: &[u8]) -> Result<u64> {
+		Reader::new(bytes, true).read_uleb128()
+	}
+
+	fn sleb128(bytes: &[u8]) -> Result<i64> {
+		Reader::new(bytes, true).read_sleb128()
+	}
+
+	#[test]
+	fn reads_uleb128() {
+		assert_eq!(uleb128(&[0x02]).unwrap(), 2);
+		assert_eq!(uleb128(&[0x7F]).unwrap(), 127);
+		assert_eq!(uleb128(&[0x80, 0x01]).unwrap(), 128);
+		assert_eq!(uleb128(&[0xE5, 0x8E, 0x26]).unwrap(), 624_485);
+		// padded with a redundant continuation byte
+		assert_eq!(uleb128(&[0x82, 0x00]).unwrap(), 2);
+
+		let max = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01];
+		assert_eq!(uleb128(&max).unwrap(), u64::MAX);
+	}
+
+	#[test]
+	fn rejects_overflowing_uleb128() {
+		let too_wide = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+		assert_eq!(uleb128(&too_wide).unwrap_err().kind(), &ErrorKind::Malformed);
+
+		let too_long = [0x80; 11];
+		assert_eq!(uleb128(&too_long).unwrap_err().kind(), &ErrorKind::Malformed);
+
+		assert!(uleb128(&[0x80, 0x80]).is_err());
+	}
+
+	#[test]
+	fn reads_sleb128() {
+		assert_eq!(sleb128(&[0x02]).unwrap(), 2);
+		assert_eq!(sleb128(&[0x3F]).unwrap(), 63);
+		assert_eq!(sleb128(&[0x40]).unwrap(), -64);
+		assert_eq!(sleb128(&[0x7F]).unwrap(), -1);
+		assert_eq!(sleb128(&[0x80, 0x7F]).unwrap(), -128);
+		assert_eq!(sleb128(&[0xC0, 0xBB, 0x78]).unwrap(), -123_456);
+
+		let min = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F];
+		assert_eq!(sleb128(&min).unwrap(), i64::MIN);
+		let max = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+		assert_eq!(sleb128(&max).unwrap(), i64::MAX);
+	}
+
+	#[test]
+	fn rejects_overflowing_sleb128() {
+		let too_wide = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01];
+		assert_eq!(sleb128(&too_wide).unwrap_err().kind(), &ErrorKind::Malformed);
+
+		let too_long = [0xFF; 11];
+		assert_eq!(sleb128(&too_long).unwrap_err().kind(), &ErrorKind::Malformed);
+	}
+
+	#[test]
+	fn leb128_advances_past_the_encoding() {
+		let mut reader = Reader::new(&[0xE5, 0x8E, 0x26, 0x7F], true);
+
+		assert_eq!(reader.read_uleb128().unwrap(), 624_485);
+		assert_eq!(reader.read_sleb128().unwrap(), -1);
+		assert!(reader.is_empty());
+	}
+}
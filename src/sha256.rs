@@ -0,0 +1,194 @@
+const K: [u32; 64] = [
+	0x428A_2F98,
+	0x7137_4491,
+	0xB5C0_FBCF,
+	0xE9B5_DBA5,
+	0x3956_C25B,
+	0x59F1_11F1,
+	0x923F_82A4,
+	0xAB1C_5ED5,
+	0xD807_AA98,
+	0x1283_5B01,
+	0x2431_85BE,
+	0x550C_7DC3,
+	0x72BE_5D74,
+	0x80DE_B1FE,
+	0x9BDC_06A7,
+	0xC19B_F174,
+	0xE49B_69C1,
+	0xEFBE_4786,
+	0x0FC1_9DC6,
+	0x240C_A1CC,
+	0x2DE9_2C6F,
+	0x4A74_84AA,
+	0x5CB0_A9DC,
+	0x76F9_88DA,
+	0x983E_5152,
+	0xA831_C66D,
+	0xB003_27C8,
+	0xBF59_7FC7,
+	0xC6E0_0BF3,
+	0xD5A7_9147,
+	0x06CA_6351,
+	0x1429_2967,
+	0x27B7_0A85,
+	0x2E1B_2138,
+	0x4D2C_6DFC,
+	0x5338_0D13,
+	0x650A_7354,
+	0x766A_0ABB,
+	0x81C2_C92E,
+	0x9272_2C85,
+	0xA2BF_E8A1,
+	0xA81A_664B,
+	0xC24B_8B70,
+	0xC76C_51A3,
+	0xD192_E819,
+	0xD699_0624,
+	0xF40E_3585,
+	0x106A_A070,
+	0x19A4_C116,
+	0x1E37_6C08,
+	0x2748_774C,
+	0x34B0_BCB5,
+	0x391C_0CB3,
+	0x4ED8_AA4A,
+	0x5B9C_CA4F,
+	0x682E_6FF3,
+	0x748F_82EE,
+	0x78A5_636F,
+	0x84C8_7814,
+	0x8CC7_0208,
+	0x90BE_FFFA,
+	0xA450_6CEB,
+	0xBEF9_A3F7,
+	0xC671_78F2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+	0x6A09_E667,
+	0xBB67_AE85,
+	0x3C6E_F372,
+	0xA54F_F53A,
+	0x510E_527F,
+	0x9B05_688C,
+	0x1F83_D9AB,
+	0x5BE0_CD19,
+];
+
+pub struct Sha256 {
+	state: [u32; 8],
+	block: [u8; 64],
+	block_length: usize,
+	total_length: u64,
+}
+
+impl Default for Sha256 {
+	fn default() -> Self {
+		Sha256 {
+			state: INITIAL_STATE,
+			block: [0; 64],
+			block_length: 0,
+			total_length: 0,
+		}
+	}
+}
+
+impl Sha256 {
+	pub fn new() -> Self {
+		Sha256::default()
+	}
+
+	pub fn digest(data: &[u8]) -> [u8; 32] {
+		let mut hasher = Sha256::new();
+		hasher.update(data);
+		hasher.finish()
+	}
+
+	pub fn update(&mut self, mut data: &[u8]) {
+		self.total_length += data.len() as u64;
+
+		while !data.is_empty() {
+			let count = (64 - self.block_length).min(data.len());
+			self.block[self.block_length..self.block_length + count]
+				.copy_from_slice(&data[..count]);
+			self.block_length += count;
+			data = &data[count..];
+
+			if self.block_length == 64 {
+				self.compress();
+				self.block_length = 0;
+			}
+		}
+	}
+
+	pub fn finish(mut self) -> [u8; 32] {
+		let bit_length = self.total_length * 8;
+
+		self.block[self.block_length] = 0x80;
+		self.block_length += 1;
+
+		if self.block_length > 56 {
+			self.block[self.block_length..].fill(0);
+			self.compress();
+			self.block_length = 0;
+		}
+
+		self.block[self.block_length..56].fill(0);
+		self.block[56..].copy_from_slice(&bit_length.to_be_bytes());
+		self.compress();
+
+		let mut digest = [0; 32];
+		for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+			chunk.copy_from_slice(&word.to_be_bytes());
+		}
+		digest
+	}
+
+	fn compress(&mut self) {
+		let mut schedule = [0u32; 64];
+		for (word, chunk) in schedule.iter_mut().zip(self.block.chunks_exact(4)) {
+			*word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+		}
+		for index in 16..64 {
+			let s0 = schedule[index - 15].rotate_right(7)
+				^ schedule[index - 15].rotate_right(18)
+				^ (schedule[index - 15] >> 3);
+			let s1 = schedule[index - 2].rotate_right(17)
+				^ schedule[index - 2].rotate_right(19)
+				^ (schedule[index - 2] >> 10);
+			schedule[index] = schedule[index - 16]
+				.wrapping_add(s0)
+				.wrapping_add(schedule[index - 7])
+				.wrapping_add(s1);
+		}
+
+		let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+
+		for index in 0..64 {
+			let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+			let choice = (e & f) ^ (!e & g);
+			let temp1 = h
+				.wrapping_add(s1)
+				.wrapping_add(choice)
+				.wrapping_add(K[index])
+				.wrapping_add(schedule[index]);
+			let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+			let majority = (a & b) ^ (a & c) ^ (b & c);
+			let temp2 = s0.wrapping_add(majority);
+
+			h = g;
+			g = f;
+			f = e;
+			e = d.wrapping_add(temp1);
+			d = c;
+			c = b;
+			b = a;
+			a = temp1.wrapping_add(temp2);
+		}
+
+		for (state, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+			*state = state.wrapping_add(value);
+		}
+	}
+}
@@ -0,0 +1,43 @@
+use std::{fs::File, path::PathBuf};
+
+use crate::elf::Elf;
+
+// the files in `fixtures/` are built by its makefile
+pub(crate) fn fixture_path(name: &str) -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name)
+}
+
+pub(crate) fn fixture_bytes(name: &str) -> Vec<u8> {
+	std::fs::read(fixture_path(name)).unwrap()
+}
+
+pub(crate) fn fixture(name: &str) -> Elf {
+	Elf::parse(File::open(fixture_path(name)).unwrap()).unwrap()
+}
+
+// for corrupting a copy of a fixture, all of them are little endian
+pub(crate) fn read_u64_at(bytes: &[u8], offset: usize) -> u64 {
+	u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+pub(crate) fn write_u16_at(bytes: &mut [u8], offset: usize, value: u16) {
+	bytes[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+pub(crate) fn write_u32_at(bytes: &mut [u8], offset: usize, value: u32) {
+	bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+pub(crate) fn write_u64_at(bytes: &mut [u8], offset: usize, value: u64) {
+	bytes[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+// the offset of a section's header, entries are 64 bytes starting at `e_shoff`
+pub(crate) fn section_header_at(bytes: &[u8], index: usize) -> usize {
+	read_u64_at(bytes, 0x28) as usize + index * 64
+}
+
+// program header entries are 56 bytes starting at `e_phoff`
+pub(crate) fn program_header_at(bytes: &[u8], index: usize) -> usize {
+	read_u64_at(bytes, 0x20) as usize + index * 56
+}